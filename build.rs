@@ -0,0 +1,23 @@
+// ============================================================================
+// Script de compilación: hash de git para `launcher --version`
+// ============================================================================
+use std::process::Command;
+
+fn main() {
+    // Sin git (p. ej. compilando desde un tarball) simplemente no hay hash
+    let hash = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()
+        .filter(|out| out.status.success())
+        .and_then(|out| String::from_utf8(out.stdout).ok())
+        .map(|hash| hash.trim().to_string())
+        .filter(|hash| !hash.is_empty());
+    if let Some(hash) = hash {
+        println!("cargo:rustc-env=LAUNCHER_GIT_HASH={}", hash);
+    }
+
+    // Recompilar al cambiar de commit
+    println!("cargo:rerun-if-changed=.git/HEAD");
+    println!("cargo:rerun-if-changed=.git/refs/heads");
+}
@@ -0,0 +1,2954 @@
+//! Carga de la configuración y lanzamiento de grupos del launcher.
+//!
+//! El binario `launcher` añade la TUI y la línea de comandos encima de esto;
+//! otras herramientas pueden usar la biblioteca para leer la misma
+//! configuración y lanzar grupos sin interfaz.
+
+// ============================================================================
+// IMPORTS
+// ============================================================================
+use ratatui::{crossterm::event::KeyCode, style::Color};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use sysinfo::{ProcessRefreshKind, ProcessesToUpdate, System, UpdateKind};
+
+// ============================================================================
+// CONFIGURACIÓN - Grupos, apps y opciones
+// ============================================================================
+
+/// Representa una aplicación dentro de un grupo
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct AppCommand {
+    /// Nombre mostrado en la UI
+    pub name: String,
+    /// Comando a ejecutar
+    pub command: String,
+    /// Argumentos opcionales (ej: ["-c", "docker start $(docker ps -aq)"]).
+    /// Un argumento `{ "file": "/ruta" }` toma el contenido de ese archivo
+    #[serde(default)]
+    pub args: Vec<Arg>,
+    /// Si true, usa `sh -c` para ejecutar (para comandos complejos)
+    #[serde(default)]
+    pub use_shell: bool,
+    /// Directorio de trabajo opcional (admite `~`)
+    #[serde(default)]
+    pub cwd: Option<String>,
+    /// Pausa opcional (en ms) después de lanzar esta app, antes de la siguiente
+    #[serde(default)]
+    pub delay_ms: Option<u64>,
+    /// Si true, esperar a que la app termine antes de seguir y reportar su
+    /// código de salida (para tareas cortas, no servicios)
+    #[serde(default)]
+    pub wait: bool,
+    /// Si false, la app se omite al lanzar el grupo
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Tiempo (en ms) que la app debe seguir viva para considerarse iniciada;
+    /// si antes termina con error se reporta como fallida
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Archivo donde guardar la salida estándar; `"inherit"` usa la terminal.
+    /// Si se omite, la salida se descarta para no ensuciar la TUI
+    #[serde(default)]
+    pub stdout: Option<String>,
+    /// Igual que `stdout`, para la salida de errores
+    #[serde(default)]
+    pub stderr: Option<String>,
+    /// Shell para esta app con `use_shell`, en lugar de la global
+    #[serde(default)]
+    pub shell: Option<String>,
+    /// Desacoplar la app de la sesión del launcher, en lugar del valor global
+    #[serde(default)]
+    pub detach: Option<bool>,
+    /// Reintentos si la app no se puede lanzar (0 = ninguno)
+    #[serde(default)]
+    pub retries: u32,
+    /// Pausa (en ms) antes de cada reintento
+    #[serde(default = "default_retry_delay_ms")]
+    pub retry_delay_ms: u64,
+    /// Condición que debe cumplirse antes de lanzar la app (p. ej. que la
+    /// base de datos acepte conexiones)
+    #[serde(default)]
+    pub wait_for: Option<WaitCondition>,
+    /// Posición de lanzamiento dentro del grupo (menor primero). Las apps
+    /// sin `order` van al final, en el orden del archivo
+    #[serde(default)]
+    pub order: Option<i32>,
+    /// Usuario con el que ejecutar la app, vía `sudo -u` (o el programa de
+    /// `run_as_program`). Requiere que no pida contraseña
+    #[serde(default)]
+    pub run_as: Option<String>,
+    /// Separar `command` como una línea de shell (`"git pull origin main"`)
+    /// en programa y argumentos, que van antes de `args`. Sin `use_shell`
+    #[serde(default)]
+    pub split: bool,
+    /// Tarea de una sola vez (build, setup): nunca se desacopla y la TUI
+    /// sigue su fin para mostrar ✓/✗. A diferencia de `wait`, no frena a
+    /// las apps siguientes del grupo
+    #[serde(default)]
+    pub oneshot: bool,
+    /// Volver a lanzar la app cuando termina, mientras la TUI la sigue
+    #[serde(default)]
+    pub restart: RestartPolicy,
+    /// Programa que usa la terminal (un editor, `htop`, `ssh`): corre en
+    /// primer plano con la TUI suspendida y se espera a que termine
+    #[serde(default)]
+    pub interactive: bool,
+    /// Qué es `command`: un programa (por defecto), o una URL o un archivo
+    /// que se abren con la aplicación asociada del sistema
+    #[serde(default)]
+    pub kind: AppKind,
+}
+
+/// Qué lanza una app (`kind`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AppKind {
+    /// `command` es el programa a ejecutar
+    #[default]
+    Command,
+    /// `command` es una URL (`https://...`, `mailto:...`)
+    Url,
+    /// `command` es un archivo o directorio (admite `~`)
+    Open,
+}
+
+impl AppKind {
+    /// Nombre tal como se escribe en la configuración
+    pub fn name(self) -> &'static str {
+        match self {
+            AppKind::Command => "command",
+            AppKind::Url => "url",
+            AppKind::Open => "open",
+        }
+    }
+}
+
+/// Cuándo se vuelve a lanzar una app que terminó (`restart`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "kebab-case")]
+pub enum RestartPolicy {
+    /// Nunca: la app queda terminada
+    #[default]
+    Never,
+    /// Solo si terminó con un código distinto de 0 o por una señal
+    OnFailure,
+    /// Siempre, también si terminó bien
+    Always,
+}
+
+/// Espera antes del primer reinicio; se duplica en cada uno
+const RESTART_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Espera máxima entre reinicios
+const RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+
+/// Tiempo en marcha a partir del cual una app se considera estable: si
+/// termina después, la espera de su reinicio vuelve a empezar desde 1 s
+pub const RESTART_BACKOFF_RESET: Duration = Duration::from_secs(60);
+
+impl RestartPolicy {
+    /// Si una app que terminó con `code` (`None` = por una señal) se reinicia
+    pub fn restarts(self, code: Option<i32>) -> bool {
+        match self {
+            RestartPolicy::Never => false,
+            RestartPolicy::OnFailure => code != Some(0),
+            RestartPolicy::Always => true,
+        }
+    }
+}
+
+/// Espera antes del reinicio número `restarts + 1`: 1 s, 2 s, 4 s... hasta 30 s
+pub fn restart_delay(restarts: u32) -> Duration {
+    RESTART_BACKOFF
+        .saturating_mul(2u32.saturating_pow(restarts))
+        .min(RESTART_BACKOFF_MAX)
+}
+
+/// Argumento de una app: texto, o el contenido de un archivo leído al lanzar
+/// para no dejar tokens y contraseñas en la configuración
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum Arg {
+    Text(String),
+    File {
+        /// Archivo cuyo contenido (sin espacios finales) es el argumento
+        file: String,
+        /// Contenido ya leído por `execute_command`
+        #[serde(skip)]
+        contents: Option<String>,
+    },
+}
+
+impl Arg {
+    /// Texto del argumento; de un archivo todavía sin leer, su ruta
+    pub fn as_str(&self) -> &str {
+        match self {
+            Arg::Text(text) => text,
+            Arg::File { contents: Some(contents), .. } => contents,
+            Arg::File { file, .. } => file,
+        }
+    }
+
+    /// Copia con el archivo leído, o un error que nombra el archivo
+    fn read(&self) -> io::Result<Arg> {
+        let Arg::File { file, .. } = self else {
+            return Ok(self.clone());
+        };
+        let contents = std::fs::read_to_string(expand_path(file)).map_err(|e| {
+            io::Error::new(e.kind(), format!("cannot read argument file {}: {}", file, e))
+        })?;
+        Ok(Arg::File {
+            file: file.clone(),
+            contents: Some(contents.trim_end().to_string()),
+        })
+    }
+}
+
+/// Los archivos se muestran como `"$(cat 'ruta')"`, con la ruta
+/// entrecomillada y nunca con su contenido
+impl std::fmt::Display for Arg {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Arg::Text(text) => f.write_str(text),
+            Arg::File { file, .. } => write!(f, "\"$(cat {})\"", shell_quote(file)),
+        }
+    }
+}
+
+/// Condición de `wait_for`: un puerto TCP que acepte conexiones o un comando
+/// que termine con código 0. Se indica exactamente una de las dos
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct WaitCondition {
+    /// Dirección `host:puerto` a la que debe poder conectarse
+    #[serde(default)]
+    pub tcp: Option<String>,
+    /// Comando que se repite hasta que termine con éxito
+    #[serde(default)]
+    pub command: Option<String>,
+    /// Argumentos de `command`
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Tiempo máximo de espera en ms; pasado este plazo la app falla
+    #[serde(default = "default_wait_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+fn default_wait_timeout_ms() -> u64 {
+    30_000
+}
+
+/// Variable que se pide al lanzar el grupo y reemplaza `{{name}}` en el
+/// comando, los argumentos y el directorio de sus apps
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Prompt {
+    /// Nombre de la variable, tal como aparece entre `{{ }}`
+    pub name: String,
+    /// Texto a mostrar al pedir el valor (el nombre si se omite)
+    #[serde(default)]
+    pub label: Option<String>,
+    /// Valor si no se escribe nada; sin él la variable es obligatoria
+    #[serde(default)]
+    pub default: Option<String>,
+}
+
+impl Prompt {
+    /// Texto de la pregunta: `Branch [main]`
+    pub fn question(&self) -> String {
+        let label = self.label.as_deref().unwrap_or(&self.name);
+        match &self.default {
+            Some(default) => format!("{} [{}]", label, default),
+            None => label.to_string(),
+        }
+    }
+}
+
+/// Un grupo de aplicaciones a ejecutar juntas
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Group {
+    pub name: String,
+    #[serde(default)]
+    pub apps: Vec<AppCommand>,
+    /// Subgrupos opcionales; si hay, Enter entra en ellos en vez de lanzar
+    #[serde(default)]
+    pub subgroups: Vec<Group>,
+    /// Si true, pedir confirmación antes de lanzar (grupos destructivos)
+    #[serde(default)]
+    pub confirm: bool,
+    /// Si true, antes de lanzar se buscan procesos con el nombre de los
+    /// programas del grupo y, si ya hay alguno, se pregunta si lanzar igual
+    #[serde(default)]
+    pub check_running: bool,
+    /// Si true, las apps se lanzan en orden, una tras otra
+    #[serde(default)]
+    pub sequential: bool,
+    /// Si false, el grupo se muestra deshabilitado y no se puede lanzar
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Icono o emoji opcional mostrado en lugar de `▸` en la lista
+    #[serde(default)]
+    pub icon: Option<String>,
+    /// Color (como los del `theme`) que tiñe bordes y acentos de la interfaz
+    /// mientras el grupo está seleccionado
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+    /// Categoría (pestaña) en la que aparece el grupo; sin categoría el grupo
+    /// solo aparece en la pestaña "All"
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Comando a ejecutar (hasta que termine) antes de lanzar las apps
+    #[serde(default)]
+    pub pre: Option<AppCommand>,
+    /// Comando a ejecutar después de lanzar las apps
+    #[serde(default)]
+    pub post: Option<AppCommand>,
+    /// Si true y `pre` falla, no se lanza ninguna app del grupo
+    #[serde(default)]
+    pub pre_required: bool,
+    /// Nombres de host en los que existe el grupo; en otras máquinas se omite
+    #[serde(default)]
+    pub only_on: Option<Vec<String>>,
+    /// Sistema operativo en el que existe el grupo (`linux`, `macos`, `windows`)
+    #[serde(default)]
+    pub os: Option<String>,
+    /// Expresión cron para lanzar el grupo automáticamente con `launcher serve`
+    /// (`"0 3 * * *"`, o con segundos: `"0 0 3 * * *"`)
+    #[serde(default)]
+    pub schedule: Option<String>,
+    /// Variables que se piden antes de lanzar, usadas como `{{name}}`
+    #[serde(default)]
+    pub prompts: Vec<Prompt>,
+    /// Nombres cortos con los que también se encuentra el grupo (`run dev`,
+    /// paleta). Únicos entre todos los grupos
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub aliases: Vec<String>,
+    /// Valores por defecto para las apps de este grupo y sus subgrupos,
+    /// sobre los `defaults` de la configuración
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<AppDefaults>,
+}
+
+/// Campos que las apps heredan si no los indican (`defaults` en la
+/// configuración o en un grupo). Se aplican al cargar, así que después cada
+/// app ya tiene sus valores completos
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AppDefaults {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub use_shell: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cwd: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub wait: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timeout_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stdout: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stderr: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub detach: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retries: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub retry_delay_ms: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub run_as: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub restart: Option<RestartPolicy>,
+}
+
+fn default_true() -> bool {
+    true
+}
+
+fn default_retry_delay_ms() -> u64 {
+    1000
+}
+
+impl Group {
+    /// Si `name` es el nombre del grupo o uno de sus alias
+    pub fn answers_to(&self, name: &str, ignore_case: bool) -> bool {
+        let same = |candidate: &String| {
+            if ignore_case {
+                candidate.to_lowercase() == name.to_lowercase()
+            } else {
+                candidate == name
+            }
+        };
+        same(&self.name) || self.aliases.iter().any(same)
+    }
+
+    /// Copia del grupo solo con las apps habilitadas y ordenadas por
+    /// `order`, que es lo que se lanza
+    pub fn launchable(&self) -> Group {
+        let mut group = self.clone();
+        group.apps.retain(|app| app.enabled);
+        // Ordenación estable: a igual `order` se mantiene el orden del archivo
+        group.apps.sort_by_key(|app| (app.order.is_none(), app.order));
+        group
+    }
+
+    /// Copia del grupo con `app` como única app, sin subgrupos ni hooks:
+    /// lo que se vuelve a lanzar cuando esa app se reinicia
+    pub fn with_only(&self, app: &AppCommand) -> Group {
+        Group {
+            apps: vec![app.clone()],
+            subgroups: Vec::new(),
+            pre: None,
+            post: None,
+            ..self.clone()
+        }
+    }
+
+    /// Valores por defecto de las variables de `prompts`
+    pub fn prompt_defaults(&self) -> HashMap<String, String> {
+        self.prompts
+            .iter()
+            .filter_map(|p| Some((p.name.clone(), p.default.clone()?)))
+            .collect()
+    }
+
+    /// Primera variable de `prompts` que no tiene valor en `values`
+    pub fn missing_prompt(&self, values: &HashMap<String, String>) -> Option<&Prompt> {
+        self.prompts.iter().find(|p| !values.contains_key(&p.name))
+    }
+
+    /// Copia del grupo con `{{name}}` reemplazado por su valor en el comando,
+    /// los argumentos y el directorio de las apps y los hooks. Las variables
+    /// sin valor quedan tal cual. La copia ya no tiene `prompts`
+    pub fn with_values(&self, values: &HashMap<String, String>) -> Group {
+        let fill = |app: &mut AppCommand| {
+            app.command = fill_placeholders(&app.command, values);
+            for arg in &mut app.args {
+                match arg {
+                    Arg::Text(text) | Arg::File { file: text, .. } => {
+                        *text = fill_placeholders(text, values)
+                    }
+                }
+            }
+            if let Some(cwd) = &mut app.cwd {
+                *cwd = fill_placeholders(cwd, values);
+            }
+        };
+        let mut group = self.clone();
+        group.apps.iter_mut().for_each(fill);
+        group.pre.iter_mut().chain(group.post.iter_mut()).for_each(fill);
+        group.prompts.clear();
+        group
+    }
+
+    /// Ambos se comparan sin distinguir mayúsculas
+    fn applies_here(&self, hostname: Option<&str>) -> bool {
+        let host_ok = self.only_on.as_ref().is_none_or(|hosts| {
+            hostname.is_some_and(|name| hosts.iter().any(|h| h.eq_ignore_ascii_case(name)))
+        });
+        let os_ok = self
+            .os
+            .as_ref()
+            .is_none_or(|os| os.eq_ignore_ascii_case(std::env::consts::OS));
+        host_ok && os_ok
+    }
+}
+
+/// Quitar (también entre los subgrupos) los grupos que no son para esta máquina
+fn retain_for_host(groups: &mut Vec<Group>, hostname: Option<&str>) {
+    groups.retain(|g| g.applies_here(hostname));
+    for group in groups {
+        retain_for_host(&mut group.subgroups, hostname);
+    }
+}
+
+/// Configuración general del launcher
+#[derive(Debug, Serialize, Deserialize, JsonSchema)]
+pub struct Config {
+    /// Versión del formato de configuración (1 si se omite)
+    #[serde(default = "default_config_version")]
+    pub version: u32,
+    /// Configuración base (admite `~`; relativa al directorio de este archivo).
+    /// Sus grupos se cargan primero y los de este archivo se agregan encima.
+    /// Solo se heredan los grupos: el tema, las teclas, `shell`, `defaults`,
+    /// `log_file`, `launch_workers` y demás opciones son las de este archivo
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extends: Option<String>,
+    pub groups: Vec<Group>,
+    /// Teclas asignadas a cada acción (opcional)
+    #[serde(default)]
+    pub keybindings: Keybindings,
+    /// Colores de la interfaz (opcional)
+    #[serde(default)]
+    pub theme: Theme,
+    /// Archivo donde registrar cada lanzamiento (opcional, admite `~`)
+    #[serde(default)]
+    pub log_file: Option<String>,
+    /// Intervalo (en ms) de espera de eventos de teclado (250 si se omite)
+    #[serde(default)]
+    pub poll_ms: Option<u64>,
+    /// Cuántas apps se lanzan a la vez dentro de un grupo no secuencial, y
+    /// en total al lanzar todos (`A`), donde primero se reparten entre los
+    /// grupos. 1 si se omite: una tras otra, en el orden del archivo
+    #[serde(default)]
+    pub launch_workers: Option<usize>,
+    /// Si false, moverse más allá del final (o del principio) de la lista se
+    /// detiene en el extremo en lugar de dar la vuelta
+    #[serde(default = "default_true")]
+    pub wrap_navigation: bool,
+    /// Capturar el ratón (clics y rueda). Con false la terminal conserva su
+    /// selección de texto para copiar y pegar
+    #[serde(default = "default_true")]
+    pub mouse: bool,
+    /// Mostrar una notificación de escritorio al lanzar cada grupo
+    #[serde(default)]
+    pub notifications: bool,
+    /// Shell para las apps con `use_shell` (`sh`, o `cmd` en Windows, si se omite)
+    #[serde(default)]
+    pub shell: Option<String>,
+    /// Desacoplar las apps de la sesión del launcher (`setsid`, o un proceso
+    /// sin consola en Windows). Algunos entornos restringidos no lo permiten
+    #[serde(default = "default_true")]
+    pub detach: bool,
+    /// Programa para `run_as`: `sudo` si se omite, o p. ej. `doas`
+    #[serde(default)]
+    pub run_as_program: Option<String>,
+    /// Socket Unix de `launcher serve` (admite `~`). Por defecto
+    /// `$XDG_RUNTIME_DIR/launcher.sock`
+    #[serde(default)]
+    pub socket: Option<String>,
+    /// Valores por defecto para todas las apps (ver `AppDefaults`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defaults: Option<AppDefaults>,
+    /// Avisos no fatales encontrados al cargar (no forman parte del archivo)
+    #[serde(skip)]
+    pub warnings: Vec<String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Config {
+            version: default_config_version(),
+            extends: None,
+            groups: Vec::new(),
+            keybindings: Keybindings::default(),
+            theme: Theme::default(),
+            log_file: None,
+            poll_ms: None,
+            launch_workers: None,
+            wrap_navigation: true,
+            mouse: true,
+            notifications: false,
+            shell: None,
+            detach: true,
+            run_as_program: None,
+            socket: None,
+            defaults: None,
+            warnings: Vec::new(),
+        }
+    }
+}
+
+/// Acciones de navegación que se pueden reasignar
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Action {
+    Up,
+    Down,
+    Select,
+    Quit,
+}
+
+/// Teclas por acción. Cada tecla es un carácter (`"j"`) o un nombre
+/// (`"Up"`, `"Enter"`, `"Esc"`...). Los valores por defecto son los de siempre
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Keybindings {
+    pub up: Vec<String>,
+    pub down: Vec<String>,
+    pub select: Vec<String>,
+    pub quit: Vec<String>,
+}
+
+impl Default for Keybindings {
+    fn default() -> Self {
+        let keys = |ks: &[&str]| ks.iter().map(|k| k.to_string()).collect();
+        Keybindings {
+            up: keys(&["Up", "k"]),
+            down: keys(&["Down", "j"]),
+            select: keys(&["Enter"]),
+            quit: keys(&["q", "Esc"]),
+        }
+    }
+}
+
+impl Keybindings {
+    /// Acción asociada a una tecla, si la hay
+    pub fn action(&self, code: KeyCode) -> Option<Action> {
+        let bound = |keys: &[String]| keys.iter().any(|k| parse_key(k) == Some(code));
+        if bound(&self.up) {
+            Some(Action::Up)
+        } else if bound(&self.down) {
+            Some(Action::Down)
+        } else if bound(&self.select) {
+            Some(Action::Select)
+        } else if bound(&self.quit) {
+            Some(Action::Quit)
+        } else {
+            None
+        }
+    }
+
+    /// Todas las teclas configuradas, con su acción, para validarlas
+    fn all(&self) -> impl Iterator<Item = (&'static str, &String)> {
+        self.up
+            .iter()
+            .map(|k| ("up", k))
+            .chain(self.down.iter().map(|k| ("down", k)))
+            .chain(self.select.iter().map(|k| ("select", k)))
+            .chain(self.quit.iter().map(|k| ("quit", k)))
+    }
+
+    /// Texto corto para el footer, p.ej. `↑/k`
+    pub fn hint(keys: &[String]) -> String {
+        keys.iter()
+            .map(|k| match k.as_str() {
+                "Up" => "↑".to_string(),
+                "Down" => "↓".to_string(),
+                other => other.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+/// Colores de la interfaz. Cada color es un nombre (`"cyan"`, `"darkgray"`...)
+/// o un valor `#RRGGBB`. Los valores por defecto reproducen el aspecto de siempre
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[serde(default)]
+pub struct Theme {
+    /// Título y elementos destacados
+    pub accent: String,
+    /// Texto del grupo seleccionado
+    pub selected_fg: String,
+    /// Fondo del grupo seleccionado
+    pub selected_bg: String,
+    /// Bordes de los paneles
+    pub border: String,
+    /// Borde de la lista y pie mientras se está buscando
+    pub search: String,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Theme {
+            accent: "cyan".to_string(),
+            selected_fg: "black".to_string(),
+            selected_bg: "cyan".to_string(),
+            border: "cyan".to_string(),
+            search: "yellow".to_string(),
+        }
+    }
+}
+
+impl Theme {
+    pub fn accent(&self) -> Color {
+        parse_color(&self.accent).unwrap_or(Color::Cyan)
+    }
+
+    pub fn selected_fg(&self) -> Color {
+        parse_color(&self.selected_fg).unwrap_or(Color::Black)
+    }
+
+    pub fn selected_bg(&self) -> Color {
+        parse_color(&self.selected_bg).unwrap_or(Color::Cyan)
+    }
+
+    pub fn border(&self) -> Color {
+        parse_color(&self.border).unwrap_or(Color::Cyan)
+    }
+
+    pub fn search(&self) -> Color {
+        parse_color(&self.search).unwrap_or(Color::Yellow)
+    }
+
+    /// Copia del tema con `accent`, `border` y `selected_bg` en `color` (el
+    /// `color` de un grupo). Un color desconocido deja el tema como está
+    pub fn tinted(&self, color: &str) -> Theme {
+        if parse_color(color).is_none() {
+            return self.clone();
+        }
+        Theme {
+            accent: color.to_string(),
+            selected_bg: color.to_string(),
+            border: color.to_string(),
+            ..self.clone()
+        }
+    }
+
+    /// Todos los colores configurados, con su campo, para validarlos
+    fn all(&self) -> [(&'static str, &String); 5] {
+        [
+            ("accent", &self.accent),
+            ("selected_fg", &self.selected_fg),
+            ("selected_bg", &self.selected_bg),
+            ("border", &self.border),
+            ("search", &self.search),
+        ]
+    }
+}
+
+/// Convertir un nombre de color o `#RRGGBB` en un `Color`.
+/// Los nombres no distinguen mayúsculas ni guiones (`LightBlue`, `light-blue`)
+fn parse_color(value: &str) -> Option<Color> {
+    if let Some(hex) = value.strip_prefix('#') {
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return None;
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).ok();
+        return Some(Color::Rgb(channel(0)?, channel(2)?, channel(4)?));
+    }
+    let name: String = value
+        .chars()
+        .filter(|c| !matches!(c, '-' | '_' | ' '))
+        .collect::<String>()
+        .to_lowercase();
+    match name.as_str() {
+        "reset" | "default" => Some(Color::Reset),
+        "black" => Some(Color::Black),
+        "red" => Some(Color::Red),
+        "green" => Some(Color::Green),
+        "yellow" => Some(Color::Yellow),
+        "blue" => Some(Color::Blue),
+        "magenta" => Some(Color::Magenta),
+        "cyan" => Some(Color::Cyan),
+        "gray" | "grey" => Some(Color::Gray),
+        "darkgray" | "darkgrey" => Some(Color::DarkGray),
+        "lightred" => Some(Color::LightRed),
+        "lightgreen" => Some(Color::LightGreen),
+        "lightyellow" => Some(Color::LightYellow),
+        "lightblue" => Some(Color::LightBlue),
+        "lightmagenta" => Some(Color::LightMagenta),
+        "lightcyan" => Some(Color::LightCyan),
+        "white" => Some(Color::White),
+        _ => None,
+    }
+}
+
+/// Convertir un nombre de tecla de la configuración en un `KeyCode`
+fn parse_key(name: &str) -> Option<KeyCode> {
+    let mut chars = name.chars();
+    if let (Some(c), None) = (chars.next(), chars.next()) {
+        return Some(KeyCode::Char(c));
+    }
+    match name {
+        "Up" => Some(KeyCode::Up),
+        "Down" => Some(KeyCode::Down),
+        "Left" => Some(KeyCode::Left),
+        "Right" => Some(KeyCode::Right),
+        "Enter" => Some(KeyCode::Enter),
+        "Esc" => Some(KeyCode::Esc),
+        "Tab" => Some(KeyCode::Tab),
+        "Backspace" => Some(KeyCode::Backspace),
+        "Space" => Some(KeyCode::Char(' ')),
+        "Home" => Some(KeyCode::Home),
+        "End" => Some(KeyCode::End),
+        "PageUp" => Some(KeyCode::PageUp),
+        "PageDown" => Some(KeyCode::PageDown),
+        _ => None,
+    }
+}
+
+/// Versión más reciente del formato de configuración que entiende este binario
+pub const CONFIG_VERSION: u32 = 1;
+
+fn default_config_version() -> u32 {
+    1
+}
+
+/// Formatos de configuración soportados, según la extensión del archivo
+const SUPPORTED_FORMATS: &str = ".json, .jsonc, .json5, .yaml, .yml, .toml";
+
+/// Extensiones que se prueban en cada directorio de búsqueda
+const CONFIG_EXTENSIONS: [&str; 6] = ["json", "jsonc", "json5", "yaml", "yml", "toml"];
+
+/// Variable de entorno con la ruta de la configuración; reemplaza toda la búsqueda
+pub const CONFIG_ENV: &str = "LAUNCHER_CONFIG";
+
+/// Intervalo de espera de eventos por defecto
+const DEFAULT_POLL: Duration = Duration::from_millis(250);
+
+impl Config {
+    /// Intervalo de espera de eventos en el bucle de la TUI
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_ms.map_or(DEFAULT_POLL, Duration::from_millis)
+    }
+
+    /// Hilos que reparten los grupos al lanzar todos
+    pub fn launch_workers(&self) -> usize {
+        self.launch_workers.unwrap_or(1).max(1)
+    }
+
+    /// Cargar configuración desde un archivo (JSON, JSONC, JSON5, YAML o TOML
+    /// según la extensión), con los grupos de la cadena de `extends` ya combinados
+    pub fn load(path: &PathBuf) -> io::Result<Self> {
+        Self::load_extending(path, &mut HashSet::new())
+    }
+
+    /// `load` siguiendo `extends`. `visited` guarda los archivos ya abiertos
+    /// en la cadena para rechazar los ciclos
+    fn load_extending(path: &PathBuf, visited: &mut HashSet<PathBuf>) -> io::Result<Self> {
+        let canonical = std::fs::canonicalize(path).unwrap_or_else(|_| path.clone());
+        if !visited.insert(canonical) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("circular extends: {} is already being loaded", path.display()),
+            ));
+        }
+
+        let mut config = Self::load_file(path)?;
+        let Some(base) = config.extends.take() else {
+            return Ok(config);
+        };
+        let base_path = match path.parent() {
+            Some(dir) => dir.join(expand_path(&base)),
+            None => expand_path(&base),
+        };
+        let base = Self::load_extending(&base_path, visited).map_err(|e| {
+            io::Error::new(e.kind(), format!("{} (extended by {})", e, path.display()))
+        })?;
+        config.inherit(base);
+        Ok(config)
+    }
+
+    /// Poner los grupos de `base` debajo de los propios. Un grupo con el
+    /// mismo nombre que uno de la base no se duplica: sus apps y subgrupos se
+    /// agregan al final de los de la base, que conserva sus demás opciones
+    fn inherit(&mut self, base: Config) {
+        let own = std::mem::replace(&mut self.groups, base.groups);
+        for group in own {
+            match self.groups.iter_mut().find(|g| g.name == group.name) {
+                Some(existing) => {
+                    existing.apps.extend(group.apps);
+                    existing.subgroups.extend(group.subgroups);
+                }
+                None => self.groups.push(group),
+            }
+        }
+        self.warnings.splice(0..0, base.warnings);
+    }
+
+    /// Leer un único archivo, sin seguir `extends`
+    fn load_file(path: &PathBuf) -> io::Result<Self> {
+        let extension = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(|e| e.to_ascii_lowercase())
+            .unwrap_or_default();
+
+        let invalid = |e: String| io::Error::new(io::ErrorKind::InvalidData, e);
+
+        // Validar la extensión antes de leer el archivo
+        if !CONFIG_EXTENSIONS.contains(&extension.as_str()) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "Unsupported config format for {}. Supported formats: {}",
+                    path.display(),
+                    SUPPORTED_FORMATS
+                ),
+            ));
+        }
+
+        let contents = std::fs::read_to_string(path)?;
+        let mut config: Self = match extension.as_str() {
+            "yaml" | "yml" => serde_yaml::from_str(&contents).map_err(|e| invalid(e.to_string()))?,
+            "toml" => toml::from_str(&contents).map_err(|e| invalid(e.to_string()))?,
+            "json5" => json5::from_str(&contents).map_err(|e| invalid(e.to_string()))?,
+            "jsonc" => serde_json::from_str(&strip_json_comments(&contents))
+                .map_err(|e| invalid(e.to_string()))?,
+            _ => serde_json::from_str(&contents).map_err(|e| invalid(e.to_string()))?,
+        };
+
+        // Con `defaults` se vuelve a leer sin tipos para saber qué campos
+        // faltan en cada app. La primera lectura ya validó el formato, con
+        // los errores apuntando a su línea
+        if config.defaults.is_some() || has_group_defaults(&config.groups) {
+            let value = match extension.as_str() {
+                "yaml" | "yml" => serde_yaml::from_str(&contents).map_err(|e| e.to_string()),
+                "toml" => toml::from_str(&contents).map_err(|e| e.to_string()),
+                "json5" => json5::from_str(&contents).map_err(|e| e.to_string()),
+                "jsonc" => serde_json::from_str(&strip_json_comments(&contents))
+                    .map_err(|e| e.to_string()),
+                _ => serde_json::from_str(&contents).map_err(|e| e.to_string()),
+            };
+            let mut value: Value = value.map_err(invalid)?;
+            apply_defaults(&mut value);
+            config = serde_json::from_value(value).map_err(|e| invalid(e.to_string()))?;
+        }
+
+        // Un formato más nuevo podría significar algo distinto: mejor rechazarlo.
+        // Las versiones anteriores se aceptan (aquí irían las migraciones)
+        if config.version > CONFIG_VERSION {
+            return Err(invalid(format!(
+                "config version {} requires a newer launcher (this build supports up to version {})",
+                config.version, CONFIG_VERSION
+            )));
+        }
+
+        // Un mismo archivo sirve para varias máquinas: los grupos con
+        // `only_on`/`os` que no coinciden se descartan al cargar
+        let hostname = hostname::get().ok().and_then(|h| h.into_string().ok());
+        retain_for_host(&mut config.groups, hostname.as_deref());
+
+        Ok(config)
+    }
+
+    /// Escribir la configuración en `json`, `yaml` o `toml`, con todos los
+    /// campos (también los que tomaron su valor por defecto)
+    pub fn serialize(&self, format: &str) -> Result<String, String> {
+        match format {
+            "yaml" | "yml" => serde_yaml::to_string(self).map_err(|e| e.to_string()),
+            "toml" => toml::to_string_pretty(self).map_err(|e| e.to_string()),
+            _ => serde_json::to_string_pretty(self)
+                .map(|text| text + "\n")
+                .map_err(|e| e.to_string()),
+        }
+    }
+
+    /// Cargar configuración desde ubicación estándar
+    /// 1. `./launcher.{json,jsonc,json5,yaml,yml,toml}`
+    /// 2. `~/.config/launcher/config.{json,jsonc,json5,yaml,yml,toml}`
+    /// 3. `/etc/launcher/config.{json,jsonc,json5,yaml,yml,toml}`
+    ///
+    /// Con `prefer_global` se busca en 2 antes que en 1.
+    /// Después se agregan los grupos de `~/.config/launcher/conf.d/*`.
+    /// Devuelve también la ruta usada, para poder recargarla después
+    pub fn load_default(prefer_global: bool) -> io::Result<(Self, PathBuf)> {
+        let base = match search_paths(prefer_global).into_iter().find(|p| p.exists()) {
+            Some(path) => Some((Self::load(&path)?, path)),
+            None => None,
+        };
+
+        // Fragmentos de conf.d: se pueden usar con o sin archivo base
+        let conf_d = conf_d_dir();
+        let fragments = match &conf_d {
+            Some(dir) if dir.is_dir() => conf_d_files(dir)?,
+            _ => Vec::new(),
+        };
+
+        let (mut config, path) = match (base, conf_d) {
+            (Some(base), _) => base,
+            (None, Some(dir)) if !fragments.is_empty() => (Self::default(), dir),
+            _ => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "No config file found. Create launcher.json in current directory.",
+                ))
+            }
+        };
+
+        for fragment in fragments {
+            config.merge_fragment(&fragment)?;
+        }
+
+        Ok((config, path))
+    }
+
+    /// Agregar los grupos de un archivo de conf.d.
+    /// Un grupo cuyo nombre ya existe se descarta con un aviso
+    fn merge_fragment(&mut self, path: &PathBuf) -> io::Result<()> {
+        let fragment = Self::load(path)?;
+        for group in fragment.groups {
+            if self.groups.iter().any(|g| g.name == group.name) {
+                self.warnings.push(format!(
+                    "{}: group {:?} already defined, skipped",
+                    path.display(),
+                    group.name
+                ));
+            } else {
+                self.groups.push(group);
+            }
+        }
+        Ok(())
+    }
+
+    /// Cargar desde una ruta explícita, si no desde `$LAUNCHER_CONFIG` y si
+    /// no desde las ubicaciones estándar (ver `load_default`)
+    pub fn load_from(path: Option<&PathBuf>, prefer_global: bool) -> io::Result<(Self, PathBuf)> {
+        let from_env = std::env::var_os(CONFIG_ENV)
+            .filter(|v| !v.is_empty())
+            .map(|v| expand_path(&v.to_string_lossy()));
+        let (mut config, path) = match (path, from_env) {
+            (Some(path), _) => (Self::load(path)?, path.clone()),
+            (None, Some(path)) if !path.exists() => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    format!("{} points to a missing file: {}", CONFIG_ENV, path.display()),
+                ))
+            }
+            (None, Some(path)) => (Self::load(&path)?, path),
+            (None, None) => Self::load_default(prefer_global)?,
+        };
+        let mut warnings = Vec::new();
+        lint_groups(&config.groups, "", &mut warnings);
+        config.warnings.extend(warnings);
+        Ok((config, path))
+    }
+
+    /// Cargar y validar en un solo paso, con los problemas en un único mensaje
+    pub fn load_validated(
+        path: Option<&PathBuf>,
+        prefer_global: bool,
+    ) -> Result<(Self, PathBuf), String> {
+        let (config, path) = Self::load_from(path, prefer_global).map_err(|e| e.to_string())?;
+        config.validate().map_err(|problems| problems.join("; "))?;
+        Ok((config, path))
+    }
+
+    /// Ruta del socket de control: la configurada, o `launcher.sock` en el
+    /// directorio de ejecución del usuario (o en el temporal si no hay)
+    pub fn socket_path(&self) -> PathBuf {
+        match &self.socket {
+            Some(path) => expand_path(path),
+            None => dirs::runtime_dir()
+                .unwrap_or_else(std::env::temp_dir)
+                .join("launcher.sock"),
+        }
+    }
+
+    /// Validar la configuración cargada.
+    /// Devuelve todos los problemas encontrados, indicando grupo y app
+    pub fn validate(&self) -> Result<(), Vec<String>> {
+        let mut problems = Vec::new();
+        validate_groups(&self.groups, "", &mut problems);
+        validate_aliases(&self.groups, &mut problems);
+
+        for (action, key) in self.keybindings.all() {
+            if parse_key(key).is_none() {
+                problems.push(format!("keybindings.{}: unknown key {:?}", action, key));
+            }
+        }
+
+        if self.shell.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            problems.push("shell: must not be empty".to_string());
+        }
+
+        if self.run_as_program.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            problems.push("run_as_program: must not be empty".to_string());
+        }
+
+        if self.socket.as_deref().is_some_and(|s| s.trim().is_empty()) {
+            problems.push("socket: must not be empty".to_string());
+        }
+
+        if self.poll_ms == Some(0) {
+            problems.push("poll_ms: must be greater than 0".to_string());
+        }
+
+        if self.launch_workers == Some(0) {
+            problems.push("launch_workers: must be greater than 0".to_string());
+        }
+
+        for (field, color) in self.theme.all() {
+            if parse_color(color).is_none() {
+                problems.push(format!("theme.{}: unknown color {:?}", field, color));
+            }
+        }
+
+        if problems.is_empty() {
+            Ok(())
+        } else {
+            Err(problems)
+        }
+    }
+}
+
+/// Si algún grupo (o subgrupo) tiene su propio `defaults`
+fn has_group_defaults(groups: &[Group]) -> bool {
+    groups.iter().any(|g| g.defaults.is_some() || has_group_defaults(&g.subgroups))
+}
+
+/// Completar cada app (y los hooks `pre`/`post`) con los campos de `defaults`
+/// que no indica, sobre el documento sin tipar. Los `defaults` de un grupo
+/// se suman a los heredados y ganan sobre ellos
+fn apply_defaults(config: &mut Value) {
+    let global = config
+        .get("defaults")
+        .and_then(Value::as_object)
+        .cloned()
+        .unwrap_or_default();
+    if let Some(groups) = config.get_mut("groups").and_then(Value::as_array_mut) {
+        apply_group_defaults(groups, &global);
+    }
+}
+
+/// Un nivel de grupos de `apply_defaults` (y sus subgrupos, recursivamente)
+fn apply_group_defaults(groups: &mut [Value], inherited: &Map<String, Value>) {
+    let fill = |app: &mut Map<String, Value>, defaults: &Map<String, Value>| {
+        for (key, value) in defaults {
+            app.entry(key.clone()).or_insert_with(|| value.clone());
+        }
+    };
+    for group in groups.iter_mut().filter_map(Value::as_object_mut) {
+        let mut defaults = inherited.clone();
+        if let Some(own) = group.get("defaults").and_then(Value::as_object) {
+            defaults.extend(own.clone());
+        }
+        for hook in ["pre", "post"] {
+            if let Some(hook) = group.get_mut(hook).and_then(Value::as_object_mut) {
+                fill(hook, &defaults);
+            }
+        }
+        if let Some(apps) = group.get_mut("apps").and_then(Value::as_array_mut) {
+            for app in apps.iter_mut().filter_map(Value::as_object_mut) {
+                fill(app, &defaults);
+            }
+        }
+        if let Some(subgroups) = group.get_mut("subgroups").and_then(Value::as_array_mut) {
+            apply_group_defaults(subgroups, &defaults);
+        }
+    }
+}
+
+/// Quitar los comentarios `//` y `/* */` de un JSONC para leerlo como JSON.
+/// Los comentarios se reemplazan por espacios (conservando los saltos de
+/// línea) para que los errores sigan indicando la línea y columna correctas.
+/// Lo que está dentro de strings no se toca
+fn strip_json_comments(input: &str) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+    let mut in_string = false;
+
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('"', _) => {
+                in_string = true;
+                out.push(c);
+            }
+            ('/', Some('/')) => {
+                out.push_str("  ");
+                chars.next();
+                while let Some(&next) = chars.peek() {
+                    if next == '\n' {
+                        break;
+                    }
+                    out.push(' ');
+                    chars.next();
+                }
+            }
+            ('/', Some('*')) => {
+                out.push_str("  ");
+                chars.next();
+                while let Some(next) = chars.next() {
+                    if next == '*' && chars.peek() == Some(&'/') {
+                        chars.next();
+                        out.push_str("  ");
+                        break;
+                    }
+                    out.push(if next == '\n' { '\n' } else { ' ' });
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+/// Ubicaciones estándar de la configuración, en orden de preferencia:
+/// `launcher.*` en `local`, `config.*` en `<config_dir>/launcher` (estas
+/// dos al revés con `prefer_global`) y en `/etc/launcher`. En cada
+/// directorio las extensiones siguen el orden de `CONFIG_EXTENSIONS`
+fn default_paths(local: &Path, config_dir: Option<&Path>, prefer_global: bool) -> Vec<PathBuf> {
+    let local: Vec<PathBuf> = CONFIG_EXTENSIONS
+        .iter()
+        .map(|ext| local.join(format!("launcher.{}", ext)))
+        .collect();
+    let global: Vec<PathBuf> = config_dir
+        .into_iter()
+        .flat_map(|dir| {
+            CONFIG_EXTENSIONS
+                .iter()
+                .map(move |ext| dir.join("launcher").join(format!("config.{}", ext)))
+        })
+        .collect();
+    let mut paths = if prefer_global {
+        [global, local].concat()
+    } else {
+        [local, global].concat()
+    };
+    for ext in CONFIG_EXTENSIONS {
+        paths.push(PathBuf::from(format!("/etc/launcher/config.{}", ext)));
+    }
+    paths
+}
+
+/// Rutas que prueba `Config::load_default`, en orden; se usa la primera que
+/// existe. Las locales son relativas al directorio actual
+pub fn search_paths(prefer_global: bool) -> Vec<PathBuf> {
+    default_paths(Path::new("."), dirs::config_dir().as_deref(), prefer_global)
+}
+
+/// Directorio de fragmentos `~/.config/launcher/conf.d`
+pub fn conf_d_dir() -> Option<PathBuf> {
+    dirs::config_dir().map(|d| d.join("launcher").join("conf.d"))
+}
+
+/// Archivos de configuración de un directorio conf.d, ordenados por nombre
+fn conf_d_files(dir: &PathBuf) -> io::Result<Vec<PathBuf>> {
+    let mut files: Vec<PathBuf> = std::fs::read_dir(dir)?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|p| {
+            p.is_file()
+                && p.extension()
+                    .and_then(|e| e.to_str())
+                    .is_some_and(|e| CONFIG_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+/// Los alias no pueden repetirse ni coincidir con el nombre de otro grupo,
+/// en ningún nivel: `run <alias>` sería ambiguo
+fn validate_aliases(groups: &[Group], problems: &mut Vec<String>) {
+    fn walk<'a>(groups: &'a [Group], all: &mut Vec<&'a Group>) {
+        for group in groups {
+            all.push(group);
+            walk(&group.subgroups, all);
+        }
+    }
+    let mut all = Vec::new();
+    walk(groups, &mut all);
+
+    let names: std::collections::HashSet<&str> = all.iter().map(|g| g.name.as_str()).collect();
+    let mut seen = std::collections::HashMap::new();
+    for group in &all {
+        for alias in &group.aliases {
+            let label = format!("group {:?}: alias {:?}", group.name, alias);
+            if alias.trim().is_empty() {
+                problems.push(format!("group {:?}: alias is empty", group.name));
+            } else if *alias != group.name && names.contains(alias.as_str()) {
+                problems.push(format!("{} is the name of another group", label));
+            } else if let Some(owner) = seen.insert(alias.as_str(), group.name.as_str()) {
+                problems.push(format!("{} is already used by group {:?}", label, owner));
+            }
+        }
+    }
+}
+
+/// Validar un nivel de grupos (y sus subgrupos, recursivamente).
+/// Los nombres deben ser únicos dentro de cada nivel
+fn validate_groups(groups: &[Group], parent: &str, problems: &mut Vec<String>) {
+    let mut seen = std::collections::HashSet::new();
+
+    for (gi, group) in groups.iter().enumerate() {
+        let group_label = format!("{}group #{} ({:?})", parent, gi + 1, group.name);
+
+        if group.name.trim().is_empty() {
+            problems.push(format!("{}: name is empty", group_label));
+        } else if !seen.insert(group.name.as_str()) {
+            problems.push(format!("{}: duplicate group name", group_label));
+        }
+
+        for (ai, app) in group.apps.iter().enumerate() {
+            let app_label = format!("{}, app #{} ({:?})", group_label, ai + 1, app.name);
+            // Con use_shell el comando real va en `args`
+            if app.use_shell {
+                if app.args.iter().all(|a| a.as_str().trim().is_empty()) {
+                    problems.push(format!("{}: use_shell requires non-empty args", app_label));
+                }
+            } else if app.command.trim().is_empty() {
+                problems.push(format!("{}: command is empty", app_label));
+            }
+            if app.split && !app.use_shell && shlex::split(&app.command).is_none() {
+                problems.push(format!("{}: command has unbalanced quotes", app_label));
+            }
+            // Una app interactiva corre en primer plano, fuera del orden del
+            // lanzamiento: no puede ser un paso de un grupo secuencial
+            if app.interactive && group.sequential {
+                problems.push(format!(
+                    "{}: interactive apps can't be part of a sequential group",
+                    app_label
+                ));
+            }
+            if app.kind != AppKind::Command && (app.use_shell || app.split || !app.args.is_empty())
+            {
+                problems.push(format!(
+                    "{}: kind {:?} opens command as is, without use_shell, split or args",
+                    app_label,
+                    app.kind.name()
+                ));
+            }
+            if app.kind == AppKind::Url && !has_url_scheme(&app.command) {
+                problems.push(format!(
+                    "{}: kind \"url\" needs a URL with a scheme (https://...), got {:?}",
+                    app_label, app.command
+                ));
+            }
+            if app.run_as.as_deref().is_some_and(|u| u.trim().is_empty()) {
+                problems.push(format!("{}: run_as must not be empty", app_label));
+            }
+            if let Some(wait) = &app.wait_for {
+                match (&wait.tcp, &wait.command) {
+                    (Some(_), Some(_)) | (None, None) => problems.push(format!(
+                        "{}: wait_for needs exactly one of tcp or command",
+                        app_label
+                    )),
+                    (Some(addr), None) if !addr.contains(':') => problems.push(format!(
+                        "{}: wait_for.tcp must be host:port, got {:?}",
+                        app_label, addr
+                    )),
+                    _ => {}
+                }
+            }
+        }
+
+        if let Some(color) = group.color.as_ref().filter(|c| parse_color(c).is_none()) {
+            problems.push(format!("{}: unknown color {:?}", group_label, color));
+        }
+
+        if let Some(Err(e)) = group.schedule.as_deref().map(parse_schedule) {
+            problems.push(format!("{}: schedule: {}", group_label, e));
+        }
+
+        let mut prompts = std::collections::HashSet::new();
+        for prompt in &group.prompts {
+            if prompt.name.trim().is_empty() {
+                problems.push(format!("{}: prompt name is empty", group_label));
+            } else if !prompts.insert(prompt.name.as_str()) {
+                problems.push(format!("{}: duplicate prompt {:?}", group_label, prompt.name));
+            }
+        }
+        // Cada `{{name}}` debe tener su prompt, o quedaría sin reemplazar
+        let apps = group.apps.iter().chain(&group.pre).chain(&group.post);
+        for app in apps {
+            let args = app.args.iter().map(Arg::as_str);
+            let texts = std::iter::once(app.command.as_str()).chain(args).chain(app.cwd.as_deref());
+            for name in texts.flat_map(|t| placeholders(t)) {
+                if !prompts.contains(name) {
+                    problems.push(format!(
+                        "{}, app {:?}: {{{{{}}}}} has no matching prompt",
+                        group_label, app.name, name
+                    ));
+                }
+            }
+        }
+
+        validate_groups(&group.subgroups, &format!("{} > ", group_label), problems);
+    }
+}
+
+/// Reemplazar cada `{{name}}` de `values` en `text`
+fn fill_placeholders(text: &str, values: &HashMap<String, String>) -> String {
+    let mut text = text.to_string();
+    for (name, value) in values {
+        text = text.replace(&format!("{{{{{}}}}}", name), value);
+    }
+    text
+}
+
+/// Nombres de las variables `{{name}}` que aparecen en `text`
+fn placeholders(text: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start + 2..].find("}}") else {
+            break;
+        };
+        names.push(&rest[start + 2..start + 2 + len]);
+        rest = &rest[start + 2 + len + 2..];
+    }
+    names
+}
+
+/// Interpretar una expresión cron. Se aceptan los 5 campos clásicos
+/// (minuto, hora, día, mes, día de la semana), con los segundos a 0, o los
+/// 6-7 campos de la crate `cron` (con segundos y año opcional)
+pub fn parse_schedule(expr: &str) -> Result<cron::Schedule, String> {
+    let full = if expr.split_whitespace().count() == 5 {
+        format!("0 {}", expr)
+    } else {
+        expr.to_string()
+    };
+    // El error de `cron` señala la posición en varias líneas; basta la última
+    full.parse::<cron::Schedule>().map_err(|e| {
+        let message = e.to_string();
+        let reason = message.lines().last().unwrap_or_default().trim().to_string();
+        format!("invalid cron expression {:?}: {}", expr, reason)
+    })
+}
+
+/// Problemas no fatales de los grupos (y subgrupos): apps repetidas dentro
+/// de un mismo grupo, que confunden la vista previa y el estado
+fn lint_groups(groups: &[Group], parent: &str, warnings: &mut Vec<String>) {
+    for group in groups {
+        let label = format!("{}{}", parent, group.name);
+        let mut seen = std::collections::HashSet::new();
+        let mut reported = std::collections::HashSet::new();
+        for app in &group.apps {
+            if !seen.insert(app.name.as_str()) && reported.insert(app.name.as_str()) {
+                warnings.push(format!("group {:?}: duplicate app name {:?}", label, app.name));
+            }
+            if app.split && app.use_shell {
+                warnings.push(format!(
+                    "group {:?}, app {:?}: split has no effect with use_shell",
+                    label, app.name
+                ));
+            }
+            if app.oneshot && app.detach == Some(true) {
+                warnings.push(format!(
+                    "group {:?}, app {:?}: oneshot apps are never detached",
+                    label, app.name
+                ));
+            }
+            if app.restart != RestartPolicy::Never && (app.oneshot || app.wait || app.interactive) {
+                warnings.push(format!(
+                    "group {:?}, app {:?}: restart is ignored for oneshot, wait and \
+                     interactive apps",
+                    label, app.name
+                ));
+            }
+        }
+        lint_groups(&group.subgroups, &format!("{} › ", label), warnings);
+    }
+}
+
+// ============================================================================
+// EJECUCIÓN - Lanzamiento de grupos y comandos
+// ============================================================================
+
+/// Opciones globales que afectan a cómo se lanzan los grupos
+#[derive(Debug, Clone)]
+pub struct LaunchOptions {
+    /// Archivo de registro de lanzamientos, ya expandido
+    pub log_file: Option<PathBuf>,
+    /// Avisar con una notificación de escritorio al terminar cada grupo
+    pub notifications: bool,
+    /// Shell global para `use_shell`; `None` usa la del sistema
+    pub shell: Option<String>,
+    /// Desacoplar las apps salvo que la app indique otra cosa
+    pub detach: bool,
+    /// Programa que ejecuta las apps con `run_as` (`sudo`, `doas`...)
+    pub run_as_program: String,
+    /// Capturar la salida de las tareas `oneshot` sin `stdout`/`stderr`
+    /// propios. Quien lanza debe leerla con `OutputTail::capture`, o el
+    /// proceso se bloquea al llenarse la tubería
+    pub capture_output: bool,
+    /// Modo `--attach`: ninguna app se desacopla y se captura la salida de
+    /// todas las que no tienen `stdout`/`stderr` propios (ver `OutputTail::attach`)
+    pub attach: bool,
+    /// Apps de un grupo no secuencial que se lanzan a la vez (`launch_workers`)
+    pub launch_workers: usize,
+}
+
+impl LaunchOptions {
+    pub fn from_config(config: &Config) -> Self {
+        LaunchOptions {
+            log_file: config.log_file.as_deref().map(expand_path),
+            notifications: config.notifications,
+            shell: config.shell.clone(),
+            detach: config.detach,
+            run_as_program: config.run_as_program.clone().unwrap_or_else(|| "sudo".to_string()),
+            capture_output: false,
+            attach: false,
+            launch_workers: config.launch_workers(),
+        }
+    }
+}
+
+/// Las mismas opciones que una configuración sin ajustes globales
+impl Default for LaunchOptions {
+    fn default() -> Self {
+        LaunchOptions::from_config(&Config::default())
+    }
+}
+
+/// Pausa entre apps de un grupo secuencial, para que cada una arranque
+/// antes de lanzar la siguiente
+const SEQUENTIAL_STEP_DELAY: Duration = Duration::from_millis(500);
+
+/// Ejecutar todas las aplicaciones de un grupo con las opciones por defecto
+/// (sin registro de lanzamientos).
+/// Devuelve un resultado por app, en el mismo orden que `group.apps`
+pub fn launch_group(group: &Group) -> Vec<io::Result<Child>> {
+    launch_group_with(group, &LaunchOptions::default())
+}
+
+/// Igual que `launch_group`, con opciones globales (p.ej. las de la configuración)
+pub fn launch_group_with(group: &Group, opts: &LaunchOptions) -> Vec<io::Result<Child>> {
+    launch_group_progress(group, opts, |_, _| {})
+}
+
+/// Igual que `launch_group_with`, llamando a `on_start` con la posición
+/// (desde 0) de cada app justo antes de lanzarla, para mostrar el progreso
+pub fn launch_group_progress(
+    group: &Group,
+    opts: &LaunchOptions,
+    mut on_start: impl FnMut(usize, &AppCommand) + Send,
+) -> Vec<io::Result<Child>> {
+    if let Some(pre) = &group.pre {
+        if let Err(e) = run_hook(group, pre, opts) {
+            if group.pre_required {
+                let skipped = || io::Error::other(format!("skipped: pre hook failed: {}", e));
+                return group.apps.iter().map(|_| Err(skipped())).collect();
+            }
+        }
+    }
+
+    // Las apps interactivas se turnan la terminal: con ellas no hay reparto
+    let workers = opts.launch_workers.min(group.apps.len());
+    let pooled = !group.sequential && workers > 1 && !group.apps.iter().any(|a| a.interactive);
+    let (mut results, started) = if pooled {
+        launch_pooled(group, opts, workers, on_start)
+    } else {
+        let mut results = Vec::with_capacity(group.apps.len());
+        let mut started = Vec::with_capacity(group.apps.len());
+        for (i, app) in group.apps.iter().enumerate() {
+            started.push(Instant::now());
+            // Modo secuencial: lanzar en orden y esperar a que cada app arranque.
+            // Si una falla, las siguientes no se lanzan (podrían depender de ella)
+            if group.sequential && results.iter_mut().any(|r| !launch_succeeded(r)) {
+                results.push(Err(io::Error::other("skipped: a previous app failed")));
+                continue;
+            }
+            on_start(i, app);
+            results.push(launch_step(group, app, opts));
+        }
+        (results, started)
+    };
+
+    // En modo paralelo las apps con `timeout_ms` se vigilan todas a la vez,
+    // sin retrasar el lanzamiento de las demás
+    if !group.sequential {
+        watch_startup(&group.apps, &mut results, started);
+    }
+    // El resultado del hook posterior queda en el registro; las apps ya se
+    // lanzaron, así que no cambia el resultado del grupo
+    if let Some(post) = &group.post {
+        let _ = run_hook(group, post, opts);
+    }
+    if opts.notifications {
+        notify_launch(group, &results);
+    }
+    results
+}
+
+/// Lanzar una app del grupo: esperar su `wait_for`, ejecutarla (con
+/// reintentos), esperarla si corresponde, registrarla y hacer su pausa
+fn launch_step(group: &Group, app: &AppCommand, opts: &LaunchOptions) -> io::Result<Child> {
+    let mut res = wait_and_execute(group, app, opts);
+    if app.wait || app.interactive {
+        res = res.and_then(|mut child| child.wait().map(|_| child));
+    } else if group.sequential {
+        let delay = app.timeout_ms.map_or(SEQUENTIAL_STEP_DELAY, Duration::from_millis);
+        res = res.and_then(|child| wait_started(child, delay));
+    }
+    if let Some(path) = &opts.log_file {
+        log_launch(path, group, app, opts, &res);
+    }
+
+    // Pausa propia de la app (p.ej. para que un servicio abra su puerto)
+    if let Some(ms) = app.delay_ms {
+        std::thread::sleep(Duration::from_millis(ms));
+    }
+    res
+}
+
+/// Esperar el `wait_for` de la app, si tiene, y lanzarla con sus `retries`
+fn wait_and_execute(group: &Group, app: &AppCommand, opts: &LaunchOptions) -> io::Result<Child> {
+    match &app.wait_for {
+        Some(wait) => wait_until(wait),
+        None => Ok(()),
+    }
+    .and_then(|_| execute_with_retries(group, app, opts))
+}
+
+/// Volver a lanzar la primera app de `group` (ver `Group::with_only`) como
+/// en un lanzamiento normal: con su `wait_for`, sus `retries`, el registro y
+/// la notificación, pero sin hooks ni la pausa de `delay_ms`
+pub fn relaunch_app(group: &Group, opts: &LaunchOptions) -> io::Result<Child> {
+    let Some(app) = group.apps.first() else {
+        return Err(io::Error::other("nothing to relaunch"));
+    };
+    let res = wait_and_execute(group, app, opts);
+    if let Some(path) = &opts.log_file {
+        log_launch(path, group, app, opts, &res);
+    }
+    if opts.notifications {
+        notify_launch(group, std::slice::from_ref(&res));
+    }
+    res
+}
+
+/// Repartir las apps de un grupo no secuencial entre `workers` hilos que
+/// las toman en el orden del grupo. El `wait_for`, el `wait` o la pausa de
+/// una app solo frenan a su hilo. Los resultados quedan en el orden del grupo
+fn launch_pooled(
+    group: &Group,
+    opts: &LaunchOptions,
+    workers: usize,
+    on_start: impl FnMut(usize, &AppCommand) + Send,
+) -> (Vec<io::Result<Child>>, Vec<Instant>) {
+    let queue = Mutex::new(group.apps.iter().enumerate());
+    let on_start = Mutex::new(on_start);
+    let slots: Mutex<Vec<_>> = Mutex::new(group.apps.iter().map(|_| None).collect());
+    std::thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| loop {
+                let Some((i, app)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+                let started = Instant::now();
+                (on_start.lock().unwrap())(i, app);
+                let res = launch_step(group, app, opts);
+                slots.lock().unwrap()[i] = Some((res, started));
+            });
+        }
+    });
+    slots.into_inner().unwrap().into_iter().flatten().unzip()
+}
+
+/// Notificación de escritorio con el nombre del grupo y las apps lanzadas.
+/// Sin servidor de notificaciones (p. ej. una sesión sin escritorio) el
+/// error se ignora: el lanzamiento ya se hizo
+fn notify_launch(group: &Group, results: &[io::Result<Child>]) {
+    let launched: Vec<&str> = group
+        .apps
+        .iter()
+        .zip(results)
+        .filter(|(_, res)| res.is_ok())
+        .map(|(app, _)| app.name.as_str())
+        .collect();
+    if launched.is_empty() {
+        return;
+    }
+    let _ = notify_rust::Notification::new()
+        .appname("launcher")
+        .summary(&group.name)
+        .body(&format!("Launched {}", launched.join(", ")))
+        .show();
+}
+
+/// Pausa entre comprobaciones de `wait_for`
+const WAIT_FOR_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Esperar hasta que se cumpla la condición o pase su `timeout_ms`
+fn wait_until(wait: &WaitCondition) -> io::Result<()> {
+    let deadline = Instant::now() + Duration::from_millis(wait.timeout_ms);
+    loop {
+        if wait_condition_met(wait) {
+            return Ok(());
+        }
+        if Instant::now() >= deadline {
+            let what = match (&wait.tcp, &wait.command) {
+                (Some(addr), _) => format!("tcp {}", addr),
+                (None, Some(cmd)) => cmd.clone(),
+                (None, None) => "nothing".to_string(),
+            };
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!("wait_for timed out after {} ms: {}", wait.timeout_ms, what),
+            ));
+        }
+        std::thread::sleep(WAIT_FOR_INTERVAL);
+    }
+}
+
+/// Comprobar una vez la condición de `wait_for`
+fn wait_condition_met(wait: &WaitCondition) -> bool {
+    use std::net::{TcpStream, ToSocketAddrs};
+
+    if let Some(addr) = &wait.tcp {
+        let Ok(addrs) = addr.to_socket_addrs() else {
+            return false;
+        };
+        return addrs
+            .into_iter()
+            .any(|a| TcpStream::connect_timeout(&a, Duration::from_secs(1)).is_ok());
+    }
+    match &wait.command {
+        Some(cmd) => Command::new(cmd)
+            .args(&wait.args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok_and(|s| s.success()),
+        None => true,
+    }
+}
+
+/// Ejecutar un hook (`pre`/`post`) del grupo y esperar a que termine.
+/// Un código de salida distinto de 0 cuenta como fallo
+fn run_hook(group: &Group, hook: &AppCommand, opts: &LaunchOptions) -> io::Result<()> {
+    // Nadie lee la salida de un hook: no capturarla aunque sea `--attach`
+    let opts = &LaunchOptions {
+        attach: false,
+        ..opts.clone()
+    };
+    let res = execute_with_retries(group, hook, opts).and_then(|mut child| {
+        let status = child.wait()?;
+        if status.success() {
+            Ok(child)
+        } else {
+            Err(io::Error::other(format!("{} exited with {}", hook.name, status)))
+        }
+    });
+    if let Some(path) = &opts.log_file {
+        log_launch(path, group, hook, opts, &res);
+    }
+    res.map(|_| ())
+}
+
+/// Líneas de comando que ejecutaría el grupo, hooks incluidos, para dry-run
+pub fn group_commands(group: &Group, opts: &LaunchOptions) -> Vec<String> {
+    let hook = |name: &str, hook: &Option<AppCommand>| {
+        hook.as_ref().map(|h| format!("{}  # {}", format_command(h, opts), name))
+    };
+    hook("pre", &group.pre)
+        .into_iter()
+        .chain(group.apps.iter().map(|app| format_command(app, opts)))
+        .chain(hook("post", &group.post))
+        .collect()
+}
+
+/// Lanzar una app, reintentando hasta `retries` veces si no se puede lanzar.
+/// Cada intento fallido que se va a reintentar queda en el registro
+fn execute_with_retries(
+    group: &Group,
+    app: &AppCommand,
+    opts: &LaunchOptions,
+) -> io::Result<Child> {
+    let mut attempt = 0;
+    loop {
+        let res = execute_command(app, opts);
+        let Err(e) = &res else {
+            return res;
+        };
+        if attempt >= app.retries {
+            return res;
+        }
+        attempt += 1;
+        if let Some(path) = &opts.log_file {
+            let message = format!(
+                "attempt {}/{} failed: {}, retrying in {} ms",
+                attempt,
+                app.retries + 1,
+                e,
+                app.retry_delay_ms
+            );
+            append_log(path, group, app, &[message]);
+        }
+        std::thread::sleep(Duration::from_millis(app.retry_delay_ms));
+    }
+}
+
+/// Vigilar las apps con `timeout_ms` hasta que pase su plazo: las que terminan
+/// antes con un código distinto de 0 pasan a ser un error
+fn watch_startup(apps: &[AppCommand], results: &mut [io::Result<Child>], started: Vec<Instant>) {
+    let mut pending: Vec<usize> = (0..apps.len())
+        .filter(|&i| apps[i].timeout_ms.is_some() && !apps[i].wait && results[i].is_ok())
+        .collect();
+
+    while !pending.is_empty() {
+        pending.retain(|&i| {
+            let timeout = Duration::from_millis(apps[i].timeout_ms.unwrap_or(0));
+            let Ok(child) = &mut results[i] else {
+                return false;
+            };
+            match child.try_wait() {
+                Ok(Some(status)) if !status.success() => {
+                    results[i] = Err(io::Error::other(format!(
+                        "exited with {} before {} ms",
+                        status,
+                        timeout.as_millis()
+                    )));
+                    false
+                }
+                Ok(Some(_)) => false,
+                Ok(None) => started[i].elapsed() < timeout,
+                Err(e) => {
+                    results[i] = Err(e);
+                    false
+                }
+            }
+        });
+        if !pending.is_empty() {
+            std::thread::sleep(Duration::from_millis(50));
+        }
+    }
+}
+
+/// Si una app se lanzó bien; las que ya terminaron (p.ej. con `wait`)
+/// cuentan como fallidas si salieron con un código distinto de 0
+pub fn launch_succeeded(res: &mut io::Result<Child>) -> bool {
+    match res {
+        Ok(child) => !matches!(child.try_wait(), Ok(Some(status)) if !status.success()),
+        Err(_) => false,
+    }
+}
+
+/// Agregar una entrada al registro de lanzamientos:
+/// `<fecha ISO-8601> [<grupo>] <app>: <comando>` y el error si falló.
+/// Un fallo al escribir el registro no debe impedir el lanzamiento, se ignora
+fn log_launch(
+    path: &PathBuf,
+    group: &Group,
+    app: &AppCommand,
+    opts: &LaunchOptions,
+    res: &io::Result<Child>,
+) {
+    let mut lines = Vec::new();
+
+    // Variables de entorno sin definir que se expandieron a ""
+    if !app.use_shell {
+        let (_, _, unset) = resolve_command(app);
+        for var in unset {
+            lines.push(format!("WARNING: ${} is not set, expanded to empty", var));
+        }
+    }
+
+    let mut line = format_command(app, opts);
+    if let Err(e) = res {
+        line.push_str(&format!(" -- FAILED: {}", e));
+    }
+    lines.push(line);
+
+    append_log(path, group, app, &lines);
+}
+
+/// Agregar líneas al registro, cada una con la fecha, el grupo y la app
+fn append_log(path: &PathBuf, group: &Group, app: &AppCommand, lines: &[String]) {
+    use std::io::Write;
+
+    let timestamp = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false);
+    let prefix = format!("{} [{}] {}", timestamp, group.name, app.name);
+    if let Ok(mut file) = std::fs::OpenOptions::new().create(true).append(true).open(path) {
+        for line in lines {
+            let _ = writeln!(file, "{}: {}", prefix, line);
+        }
+    }
+}
+
+/// Esperar hasta `delay` comprobando que el proceso no termine con error
+/// inmediatamente. Un proceso que sigue vivo (o que terminó bien) cuenta como iniciado
+fn wait_started(mut child: Child, delay: Duration) -> io::Result<Child> {
+    let start = Instant::now();
+    while start.elapsed() < delay {
+        if let Some(status) = child.try_wait()? {
+            if !status.success() {
+                return Err(io::Error::other(format!("exited with {}", status)));
+            }
+            break;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+    Ok(child)
+}
+
+/// Todos los grupos habilitados con apps que lanzar, incluidos los subgrupos,
+/// junto a su clave (la ruta de nombres, igual que `App::group_key`)
+pub fn flatten_groups(groups: &[Group]) -> Vec<(String, Group)> {
+    fn walk(groups: &[Group], prefix: &str, out: &mut Vec<(String, Group)>) {
+        for group in groups.iter().filter(|g| g.enabled) {
+            let key = if prefix.is_empty() {
+                group.name.clone()
+            } else {
+                format!("{} › {}", prefix, group.name)
+            };
+            let launchable = group.launchable();
+            if !launchable.apps.is_empty() {
+                out.push((key.clone(), launchable));
+            }
+            walk(&group.subgroups, &key, out);
+        }
+    }
+    let mut out = Vec::new();
+    walk(groups, "", &mut out);
+    out
+}
+
+/// Construir un resumen legible del lanzamiento de un grupo
+pub fn summarize_launch(group: &Group, results: &[io::Result<Child>]) -> String {
+    let failed: Vec<String> = group
+        .apps
+        .iter()
+        .zip(results)
+        .filter_map(|(app, res)| res.as_ref().err().map(|e| format!("{} ({})", app.name, e)))
+        .collect();
+    let ok = results.len() - failed.len();
+
+    if failed.is_empty() {
+        format!("Launched {} ({}/{} apps)", group.name, ok, results.len())
+    } else {
+        format!(
+            "Launched {} ({}/{} apps). Failed: {}",
+            group.name,
+            ok,
+            results.len(),
+            failed.join(", ")
+        )
+    }
+}
+
+/// Destino de `stdout`/`stderr` de una app: descartado por defecto,
+/// la terminal con `"inherit"` o un archivo (se agrega al final)
+fn output_stdio(target: &Option<String>) -> io::Result<Stdio> {
+    match target.as_deref() {
+        None => Ok(Stdio::null()),
+        Some("inherit") => Ok(Stdio::inherit()),
+        Some(path) => {
+            let path = expand_path(path);
+            if let Some(dir) = path.parent().filter(|d| !d.as_os_str().is_empty()) {
+                std::fs::create_dir_all(dir)?;
+            }
+            let file = std::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|e| io::Error::new(e.kind(), format!("{}: {}", path.display(), e)))?;
+            Ok(Stdio::from(file))
+        }
+    }
+}
+
+/// Ejecutar un comando individual de forma desacoplada de la terminal
+pub fn execute_command(app: &AppCommand, opts: &LaunchOptions) -> io::Result<Child> {
+    // Los argumentos `{ "file": ... }` se leen ahora; si falta el archivo la
+    // app no se lanza
+    let app = &AppCommand {
+        args: app.args.iter().map(Arg::read).collect::<io::Result<_>>()?,
+        ..app.clone()
+    };
+
+    if app.run_as.is_some() {
+        if cfg!(windows) {
+            return Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "run_as is not supported on Windows",
+            ));
+        }
+        if find_program(&opts.run_as_program, None).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("command not found: {}", opts.run_as_program),
+            ));
+        }
+    }
+
+    // Resolver el directorio de trabajo antes de lanzar; si no existe
+    // es un error, para no ejecutar la app en el lugar equivocado
+    let cwd = app.cwd.as_deref().map(expand_path);
+    if let Some(dir) = cwd.as_ref().filter(|dir| !dir.is_dir()) {
+        return Err(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("working directory not found: {}", dir.display()),
+        ));
+    }
+
+    if !app.use_shell {
+        // Un error claro en lugar del "No such file or directory" del sistema
+        let (program, _, _) = resolve_command(app);
+        if find_program(&program, cwd.as_deref()).is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("command not found: {}", program),
+            ));
+        }
+    }
+
+    // El programa que abre el archivo termina enseguida: mejor fallar aquí
+    if app.kind == AppKind::Open {
+        let (_, args, _) = resolve_command(app);
+        let target = PathBuf::from(args.last().map(String::as_str).unwrap_or_default());
+        let target = match &cwd {
+            Some(dir) if target.is_relative() => dir.join(target),
+            _ => target,
+        };
+        if !target.exists() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                format!("file not found: {}", target.display()),
+            ));
+        }
+    }
+
+    let mut cmd = build_command(app, opts);
+    // Las apps con `wait` se esperan antes de que nadie lea la tubería
+    let capture = !app.wait
+        && !app.interactive
+        && (opts.attach || (opts.capture_output && app.oneshot));
+    cmd.stdout(match &app.stdout {
+        None if capture => Stdio::piped(),
+        None if app.interactive => Stdio::inherit(),
+        target => output_stdio(target)?,
+    });
+    cmd.stderr(match &app.stderr {
+        None if capture => Stdio::piped(),
+        None if app.interactive => Stdio::inherit(),
+        target => output_stdio(target)?,
+    });
+    cmd.spawn()
+}
+
+/// Líneas que se guardan de la salida de una tarea `oneshot`
+pub const OUTPUT_TAIL_LINES: usize = 20;
+
+/// Una línea de salida capturada y la app que la escribió
+#[derive(Debug, Clone, PartialEq)]
+pub struct OutputLine {
+    pub source: String,
+    pub text: String,
+}
+
+/// Últimas líneas de la salida capturada de uno o varios procesos (stdout
+/// y stderr mezcladas, en orden de llegada). Se llena desde hilos propios
+/// mientras los procesos corren, así que se puede consultar en cualquier
+/// momento
+#[derive(Debug, Clone)]
+pub struct OutputTail {
+    lines: Arc<Mutex<VecDeque<OutputLine>>>,
+    readers: Arc<Mutex<Vec<std::thread::JoinHandle<()>>>>,
+    capacity: usize,
+    /// Líneas recibidas desde el principio, incluidas las ya descartadas
+    received: Arc<std::sync::atomic::AtomicUsize>,
+}
+
+impl OutputTail {
+    /// Registro vacío que guarda como mucho `capacity` líneas
+    pub fn new(capacity: usize) -> Self {
+        OutputTail {
+            lines: Arc::default(),
+            readers: Arc::default(),
+            capacity: capacity.max(1),
+            received: Arc::default(),
+        }
+    }
+
+    /// Empezar a leer la salida capturada de `child`, si la tiene, en un
+    /// registro propio de `OUTPUT_TAIL_LINES` líneas
+    pub fn capture(child: &mut Child) -> Option<Self> {
+        let tail = OutputTail::new(OUTPUT_TAIL_LINES);
+        tail.attach(child, "").then_some(tail)
+    }
+
+    /// Sumar a este registro la salida capturada de `child`, con `source`
+    /// como origen de sus líneas. Devuelve false si no tenía salida capturada
+    pub fn attach(&self, child: &mut Child, source: &str) -> bool {
+        let (stdout, stderr) = (child.stdout.take(), child.stderr.take());
+        let any = stdout.is_some() || stderr.is_some();
+        if let Some(pipe) = stdout {
+            self.read_from(pipe, source);
+        }
+        if let Some(pipe) = stderr {
+            self.read_from(pipe, source);
+        }
+        any
+    }
+
+    fn read_from(&self, pipe: impl io::Read + Send + 'static, source: &str) {
+        use std::io::BufRead;
+        let (lines, capacity) = (Arc::clone(&self.lines), self.capacity);
+        let received = Arc::clone(&self.received);
+        let source = source.to_string();
+        let reader = std::thread::spawn(move || {
+            // Sin exigir UTF-8: una línea inválida no debe cortar la lectura
+            for line in io::BufReader::new(pipe).split(b'\n') {
+                let Ok(line) = line else { break };
+                let text = String::from_utf8_lossy(&line).trim_end_matches('\r').to_string();
+                let mut lines = lines.lock().unwrap_or_else(|e| e.into_inner());
+                if lines.len() == capacity {
+                    lines.pop_front();
+                }
+                lines.push_back(OutputLine { source: source.clone(), text });
+                received.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            }
+        });
+        self.readers.lock().unwrap_or_else(|e| e.into_inner()).push(reader);
+    }
+
+    /// Cantidad de líneas recibidas hasta ahora, para saber si llegó algo nuevo
+    pub fn received(&self) -> usize {
+        self.received.load(std::sync::atomic::Ordering::Relaxed)
+    }
+
+    /// Texto de las líneas guardadas, de la más antigua a la más reciente
+    pub fn lines(&self) -> Vec<String> {
+        self.entries().into_iter().map(|line| line.text).collect()
+    }
+
+    /// Las líneas guardadas con su origen
+    pub fn entries(&self) -> Vec<OutputLine> {
+        let lines = self.lines.lock().unwrap_or_else(|e| e.into_inner());
+        lines.iter().cloned().collect()
+    }
+
+    /// Esperar a que se lea toda la salida (tras terminar el proceso), para
+    /// no perder las últimas líneas
+    pub fn finish(&self) {
+        let readers = std::mem::take(&mut *self.readers.lock().unwrap_or_else(|e| e.into_inner()));
+        for reader in readers {
+            let _ = reader.join();
+        }
+    }
+}
+
+/// Armar el `Command` de una app sin lanzarlo: programa y argumentos (con
+/// la shell y `run_as` si corresponde), directorio y desacople. No comprueba
+/// que el programa o el directorio existan ni redirige la salida
+pub fn build_command(app: &AppCommand, opts: &LaunchOptions) -> Command {
+    // Una tarea `oneshot` sigue siendo hija del launcher para esperar su fin
+    let detach =
+        !app.oneshot && !app.interactive && !opts.attach && app.detach.unwrap_or(opts.detach);
+
+    let mut cmd = if app.use_shell {
+        // Para comandos complejos con pipes, variables, etc
+        let (shell, flag) = shell_invocation(app, opts);
+        let mut cmd = new_command(shell, app, opts);
+        cmd.arg(flag).args(app.args.iter().map(Arg::as_str));
+        cmd
+    } else {
+        // Para comandos simples; las variables de entorno se expanden aquí
+        // porque no hay una shell que lo haga
+        let (program, args, _) = resolve_command(app);
+        let mut cmd = new_command(&program, app, opts);
+        cmd.args(args);
+
+        // Importante: desacoplar del padre para que la app no muera
+        // cuando cierre la terminal
+        // Esto crea una nueva sesión de proceso con setsid()
+        #[cfg(unix)]
+        if detach {
+            use std::os::unix::process::CommandExt;
+            unsafe {
+                cmd.pre_exec(|| {
+                    // Cambiar a nuevo session group. Si setsid no está
+                    // permitido (p. ej. en un sandbox) la app sigue en la
+                    // sesión del launcher en lugar de fallar el lanzamiento
+                    let _ = libc::setsid();
+                    Ok(())
+                });
+            }
+        }
+        cmd
+    };
+
+    // Con `--attach` la app queda en la sesión, pero en su propio grupo de
+    // procesos para que `terminate` cierre también sus hijos
+    // Una app interactiva se queda en el grupo de la terminal para poder leerla
+    #[cfg(unix)]
+    if opts.attach && !app.interactive {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+    #[cfg(windows)]
+    if detach {
+        detach_windows(&mut cmd);
+    }
+    // Una señal ignorada sigue ignorada después de exec, y el launcher ignora
+    // SIGINT y SIGQUIT mientras espera a una app interactiva. Una app lanzada
+    // entretanto desde otro hilo las recibe con su acción por defecto
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        unsafe {
+            cmd.pre_exec(|| {
+                libc::signal(libc::SIGINT, libc::SIG_DFL);
+                libc::signal(libc::SIGQUIT, libc::SIG_DFL);
+                Ok(())
+            });
+        }
+    }
+    if let Some(dir) = &app.cwd {
+        cmd.current_dir(expand_path(dir));
+    }
+    cmd
+}
+
+/// Comando que ejecuta `program`. Con `run_as` queda envuelto en
+/// `sudo -n -u <user>` (o el programa configurado), así que con `use_shell`
+/// es la shell entera la que corre como ese usuario. `-n` hace que falle en
+/// lugar de pedir una contraseña que nadie podría escribir
+fn new_command(program: &str, app: &AppCommand, opts: &LaunchOptions) -> Command {
+    match &app.run_as {
+        Some(user) => {
+            let mut cmd = Command::new(&opts.run_as_program);
+            cmd.args(["-n", "-u", user, program]);
+            cmd
+        }
+        None => Command::new(program),
+    }
+}
+
+/// Intérprete usado con `use_shell` y su opción para recibir un comando.
+/// Se usa la shell de la app, si no la global y si no `sh` (`cmd` en Windows)
+fn shell_invocation<'a>(app: &'a AppCommand, opts: &'a LaunchOptions) -> (&'a str, &'static str) {
+    let default = if cfg!(windows) { "cmd" } else { "sh" };
+    let shell = app.shell.as_deref().or(opts.shell.as_deref()).unwrap_or(default);
+    let name = Path::new(shell)
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or(shell)
+        .to_ascii_lowercase();
+    let flag = match name.as_str() {
+        "cmd" => "/C",
+        "powershell" | "pwsh" => "-Command",
+        _ => "-c",
+    };
+    (shell, flag)
+}
+
+/// Programa del sistema que abre URLs y archivos con su aplicación asociada.
+/// En Windows `start` es un comando interno de `cmd`, y su primer argumento
+/// entre comillas es el título de la ventana: por eso el `""`
+fn opener() -> Vec<String> {
+    let words: &[&str] = if cfg!(windows) {
+        &["cmd", "/C", "start", ""]
+    } else if cfg!(target_os = "macos") {
+        &["open"]
+    } else {
+        &["xdg-open"]
+    };
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+/// Si `text` empieza con un esquema de URL (`https:`, `mailto:`...)
+fn has_url_scheme(text: &str) -> bool {
+    text.split_once(':').is_some_and(|(scheme, _)| {
+        scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+    })
+}
+
+/// Línea de comando completa que ejecutaría `execute_command`, con los
+/// argumentos entrecomillados para poder copiarla y pegarla en una shell
+pub fn format_command(app: &AppCommand, opts: &LaunchOptions) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    if let Some(user) = &app.run_as {
+        parts.extend([shell_quote(&opts.run_as_program), "-n".to_string(), "-u".to_string()]);
+        parts.push(shell_quote(user));
+    }
+    if app.use_shell {
+        let (shell, flag) = shell_invocation(app, opts);
+        parts.push(shell_quote(shell));
+        parts.push(flag.to_string());
+        parts.extend(app.args.iter().map(quote_arg));
+    } else {
+        let (program, args, _) = resolve_command(app);
+        parts.push(shell_quote(&program));
+        // Los `args` van al final, después de las palabras de `command`
+        let own = args.len() - app.args.len();
+        parts.extend(args[..own].iter().map(|a| shell_quote(a)));
+        parts.extend(app.args.iter().zip(&args[own..]).map(|(arg, value)| match arg {
+            Arg::Text(_) => shell_quote(value),
+            Arg::File { .. } => quote_arg(arg),
+        }));
+    }
+
+    let line = parts.join(" ");
+    match &app.cwd {
+        Some(dir) => format!("cd {} && {}", shell_quote(&expand_path(dir).to_string_lossy()), line),
+        None => line,
+    }
+}
+
+/// Programa y argumentos de una app sin shell, con `$VAR`/`${VAR}` expandidas.
+/// Devuelve también los nombres de las variables referenciadas que no existen
+fn resolve_command(app: &AppCommand) -> (String, Vec<String>, Vec<String>) {
+    let mut unset = Vec::new();
+    // Con `split` las palabras de `command` se separan antes de expandir, así
+    // un valor con espacios sigue siendo un solo argumento
+    // Una URL o un archivo son el único argumento del programa que los abre
+    let words = if app.kind != AppKind::Command {
+        let mut words = opener();
+        words.push(app.command.clone());
+        words
+    } else {
+        match app.split.then(|| shlex::split(&app.command)).flatten() {
+            Some(words) if !words.is_empty() => words,
+            _ => vec![app.command.clone()],
+        }
+    };
+    let mut words: Vec<String> = words.iter().map(|w| expand_env(w, &mut unset)).collect();
+    if app.kind == AppKind::Open {
+        if let Some(target) = words.last_mut() {
+            *target = expand_home(target).to_string_lossy().into_owned();
+        }
+    }
+    // El contenido de un archivo se pasa tal cual, sin expandir variables
+    words.extend(app.args.iter().map(|arg| match arg {
+        Arg::Text(text) => expand_env(text, &mut unset),
+        Arg::File { .. } => arg.as_str().to_string(),
+    }));
+    let mut words = words.into_iter();
+    let program = words.next().unwrap_or_default();
+    let args = words.collect();
+    (program, args, unset)
+}
+
+/// Argumento para `format_command`: un archivo queda como `"$(cat 'ruta')"`
+/// (ver `Display`) para no copiar su contenido en la vista previa ni en el
+/// registro
+fn quote_arg(arg: &Arg) -> String {
+    match arg {
+        Arg::Text(text) => shell_quote(text),
+        Arg::File { .. } => arg.to_string(),
+    }
+}
+
+/// Programa de una app sin shell que no existe en el PATH (o en la ruta
+/// indicada), o `None` si se encuentra. Las apps con shell no se comprueban
+pub fn missing_binary(app: &AppCommand) -> Option<String> {
+    if app.use_shell {
+        return None;
+    }
+    let (program, _, _) = resolve_command(app);
+    let cwd = app.cwd.as_deref().map(expand_path);
+    find_program(&program, cwd.as_deref()).is_none().then_some(program)
+}
+
+/// Buscar un programa como `which`. Si contiene un separador de ruta se
+/// comprueba tal cual (o relativo a `cwd`); si no, en cada directorio del PATH
+fn find_program(program: &str, cwd: Option<&Path>) -> Option<PathBuf> {
+    let path = Path::new(program);
+    if program.is_empty() {
+        return None;
+    }
+    if path.components().count() > 1 {
+        let mut candidates = vec![path.to_path_buf()];
+        if let Some(dir) = cwd.filter(|_| path.is_relative()) {
+            candidates.push(dir.join(path));
+        }
+        return candidates.into_iter().flat_map(executable_names).find(|p| is_executable(p));
+    }
+    let dirs = std::env::var_os("PATH")?;
+    std::env::split_paths(&dirs)
+        .flat_map(|dir| executable_names(dir.join(program)))
+        .find(|p| is_executable(p))
+}
+
+/// Nombres a probar para un programa: en Windows también con cada extensión
+/// de `PATHEXT` (`.exe`, `.bat`, ...)
+fn executable_names(path: PathBuf) -> Vec<PathBuf> {
+    let mut names = vec![path.clone()];
+    if cfg!(windows) && path.extension().is_none() {
+        let exts = std::env::var("PATHEXT").unwrap_or_else(|_| ".COM;.EXE;.BAT;.CMD".into());
+        names.extend(exts.split(';').filter(|e| !e.is_empty()).map(|e| {
+            let mut name = path.clone().into_os_string();
+            name.push(e);
+            PathBuf::from(name)
+        }));
+    }
+    names
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|m| m.is_file() && m.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+/// Expandir referencias `$VAR` y `${VAR}` con el entorno del proceso.
+/// Las variables sin definir se sustituyen por "" y se anotan en `unset`
+fn expand_env(input: &str, unset: &mut Vec<String>) -> String {
+    expand_vars(input, unset, |name| std::env::var(name).ok())
+}
+
+/// Igual que `expand_env`, tomando los valores de `lookup`
+fn expand_vars(
+    input: &str,
+    unset: &mut Vec<String>,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> String {
+    let mut out = String::with_capacity(input.len());
+    let mut chars = input.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '$' {
+            out.push(c);
+            continue;
+        }
+
+        let name: String = if chars.peek() == Some(&'{') {
+            chars.next();
+            let name: String = chars.by_ref().take_while(|&c| c != '}').collect();
+            name
+        } else {
+            let mut name = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_ascii_alphanumeric() || c == '_' {
+                    name.push(c);
+                    chars.next();
+                } else {
+                    break;
+                }
+            }
+            name
+        };
+
+        // Un `$` suelto se deja tal cual
+        if name.is_empty() {
+            out.push('$');
+            continue;
+        }
+
+        match lookup(&name) {
+            Some(value) => out.push_str(&value),
+            None => {
+                if !unset.contains(&name) {
+                    unset.push(name);
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Entrecomillar un argumento para sh si contiene caracteres especiales
+fn shell_quote(arg: &str) -> String {
+    let safe = |c: char| c.is_ascii_alphanumeric() || "_@%+=:,./-".contains(c);
+    if !arg.is_empty() && arg.chars().all(safe) {
+        arg.to_string()
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// En Windows no existe setsid: se desacopla el proceso creando un nuevo
+/// grupo de procesos sin consola asociada
+#[cfg(windows)]
+fn detach_windows(cmd: &mut Command) {
+    use std::os::windows::process::CommandExt;
+    const DETACHED_PROCESS: u32 = 0x0000_0008;
+    const CREATE_NEW_PROCESS_GROUP: u32 = 0x0000_0200;
+    cmd.creation_flags(DETACHED_PROCESS | CREATE_NEW_PROCESS_GROUP);
+}
+
+/// Terminar un proceso lanzado por el launcher.
+/// Las apps desacopladas con setsid lideran su propio grupo de procesos, así que
+/// se señala el grupo completo (PID negativo) para cerrar también sus hijos;
+/// si no es líder de grupo, se señala solo el proceso
+#[cfg(unix)]
+pub fn terminate(pid: u32) -> io::Result<()> {
+    let pid = pid as libc::pid_t;
+    // SAFETY: kill solo envía una señal, no toca memoria del proceso
+    unsafe {
+        if libc::kill(-pid, libc::SIGTERM) == 0 || libc::kill(pid, libc::SIGTERM) == 0 {
+            Ok(())
+        } else {
+            Err(io::Error::last_os_error())
+        }
+    }
+}
+
+#[cfg(not(unix))]
+pub fn terminate(pid: u32) -> io::Result<()> {
+    let status = Command::new("taskkill")
+        .args(["/PID", &pid.to_string(), "/T"])
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("taskkill exited with {}", status)))
+    }
+}
+
+/// Cuántos procesos del sistema (sin contar el launcher) se llaman como el
+/// programa de alguna de las apps, para avisar antes de lanzar otra copia.
+/// Se compara el nombre del proceso y el de su ejecutable; de las apps con
+/// shell se toma la primera palabra del comando
+pub fn running_instances(apps: &[AppCommand]) -> usize {
+    let programs: Vec<String> = apps.iter().filter_map(program_name).collect();
+    if programs.is_empty() {
+        return 0;
+    }
+    let mut system = System::new();
+    let refresh = ProcessRefreshKind::nothing().with_exe(UpdateKind::OnlyIfNotSet);
+    system.refresh_processes_specifics(ProcessesToUpdate::All, true, refresh);
+    let own = sysinfo::get_current_pid().ok();
+    system
+        .processes()
+        .values()
+        .filter(|p| p.thread_kind().is_none() && Some(p.pid()) != own)
+        .filter(|p| {
+            let exe = p.exe().and_then(Path::file_name);
+            programs.iter().any(|name| p.name() == name.as_str() || exe == Some(name.as_ref()))
+        })
+        .count()
+}
+
+/// Nombre (sin directorio) del programa que ejecuta una app. Con `use_shell`
+/// es la primera palabra del script (el primer argumento que no es una
+/// opción), no la shell, que coincidiría con cualquier `sh` de la máquina.
+/// Una URL o un archivo (`kind`) no dejan un proceso propio que buscar
+fn program_name(app: &AppCommand) -> Option<String> {
+    if app.kind != AppKind::Command {
+        return None;
+    }
+    let program = if app.use_shell {
+        let script = app.args.iter().map(Arg::as_str).find(|a| !a.starts_with('-'))?;
+        let word = shlex::split(script)?.into_iter().next()?;
+        // Una asignación o una variable no dicen qué programa corre
+        if word.contains('=') || word.starts_with('$') {
+            return None;
+        }
+        word
+    } else {
+        resolve_command(app).0
+    };
+    let name = Path::new(&program).file_name()?.to_str()?;
+    Some(name.to_string())
+}
+
+/// Expandir una ruta indicada por el usuario: un `~` inicial pasa a ser el
+/// directorio home y las referencias `$VAR`/`${VAR}` se sustituyen
+pub fn expand_path(path: &str) -> PathBuf {
+    expand_home(&expand_env(path, &mut Vec::new()))
+}
+
+/// `~` y `~/...` como el directorio personal, sin expandir variables
+fn expand_home(path: &str) -> PathBuf {
+    if path == "~" {
+        return dirs::home_dir().unwrap_or_else(|| PathBuf::from(path));
+    }
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+// ============================================================================
+// TESTS
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::ffi::OsStr;
+
+    fn app(json: &str) -> AppCommand {
+        serde_json::from_str(json).unwrap()
+    }
+
+    fn args(cmd: &Command) -> Vec<&OsStr> {
+        cmd.get_args().collect()
+    }
+
+    /// Directorio temporal vacío y propio de cada test
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("launcher-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    fn extends_layers_groups_over_the_base_and_rejects_cycles() {
+        let dir = temp_dir("extends");
+        let write = |name: &str, json: &str| std::fs::write(dir.join(name), json).unwrap();
+        write(
+            "base.json",
+            r#"{"groups": [{"name": "Dev", "apps": [{"name": "a", "command": "true"}]}]}"#,
+        );
+        write(
+            "child.json",
+            r#"{"extends": "base.json", "groups": [
+                {"name": "Dev", "apps": [{"name": "b", "command": "true"}]},
+                {"name": "Ops", "apps": []}
+            ]}"#,
+        );
+        let config = Config::load(&dir.join("child.json")).unwrap();
+        let names: Vec<_> = config.groups.iter().map(|g| g.name.as_str()).collect();
+        assert_eq!(names, ["Dev", "Ops"]);
+        let apps: Vec<_> = config.groups[0].apps.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(apps, ["a", "b"]);
+        assert_eq!(config.extends, None);
+
+        write("loop.json", r#"{"extends": "./loop2.json", "groups": []}"#);
+        write("loop2.json", r#"{"extends": "loop.json", "groups": []}"#);
+        let err = Config::load(&dir.join("loop.json")).unwrap_err();
+        assert!(err.to_string().contains("circular extends"), "{}", err);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn extends_only_inherits_groups() {
+        let dir = temp_dir("extends-settings");
+        let base = r#"{"shell": "zsh", "log_file": "base.log", "launch_workers": 4,
+            "theme": {"border": "red"}, "groups": [{"name": "Dev", "apps": []}]}"#;
+        std::fs::write(dir.join("base.json"), base).unwrap();
+        std::fs::write(dir.join("child.json"), r#"{"extends": "base.json", "groups": []}"#)
+            .unwrap();
+        let config = Config::load(&dir.join("child.json")).unwrap();
+        assert_eq!(config.groups[0].name, "Dev");
+        assert_eq!(config.shell, None);
+        assert_eq!(config.log_file, None);
+        assert_eq!(config.launch_workers(), 1);
+        assert_eq!(config.theme.border, "cyan");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn app_fields_take_their_defaults() {
+        let app = app(r#"{ "name": "a", "command": "true" }"#);
+        assert!(app.args.is_empty());
+        assert!(!app.use_shell);
+        assert!(app.enabled);
+        assert!(!app.wait);
+        assert_eq!(app.cwd, None);
+        assert_eq!(app.delay_ms, None);
+        assert_eq!(app.detach, None);
+        assert_eq!(app.retries, 0);
+        assert_eq!(app.retry_delay_ms, 1000);
+        assert_eq!(app.order, None);
+    }
+
+    #[test]
+    fn config_fields_take_their_defaults() {
+        let config: Config =
+            serde_json::from_str(r#"{ "groups": [{ "name": "G", "apps": [] }] }"#).unwrap();
+        assert_eq!(config.version, CONFIG_VERSION);
+        assert!(config.detach);
+        assert!(config.wrap_navigation);
+        assert!(config.mouse);
+        assert!(!config.notifications);
+        assert_eq!(config.poll_interval(), Duration::from_millis(250));
+        assert_eq!(config.launch_workers(), 1);
+        assert_eq!(config.keybindings.quit, Keybindings::default().quit);
+        assert_eq!(config.theme.border, "cyan");
+
+        let group = &config.groups[0];
+        assert!(group.enabled);
+        assert!(!group.confirm);
+        assert!(!group.sequential);
+        assert!(group.subgroups.is_empty());
+        assert!(group.prompts.is_empty());
+    }
+
+    #[test]
+    fn default_paths_prefer_local_then_config_dir() {
+        let root = temp_dir("paths");
+        let (local, config_dir) = (root.join("cwd"), root.join("config"));
+        std::fs::create_dir_all(&local).unwrap();
+        std::fs::create_dir_all(config_dir.join("launcher")).unwrap();
+        let first = || {
+            default_paths(&local, Some(&config_dir), false)
+                .into_iter()
+                .find(|p| p.exists())
+        };
+
+        assert_eq!(first(), None);
+
+        let user = config_dir.join("launcher").join("config.yaml");
+        std::fs::write(&user, "groups: []").unwrap();
+        assert_eq!(first(), Some(user.clone()));
+
+        let toml = local.join("launcher.toml");
+        std::fs::write(&toml, "groups = []").unwrap();
+        assert_eq!(first(), Some(toml));
+
+        // En un mismo directorio JSON va antes que TOML
+        let json = local.join("launcher.json");
+        std::fs::write(&json, r#"{ "groups": [] }"#).unwrap();
+        assert_eq!(first(), Some(json));
+
+        // Con prefer_global la configuración del usuario gana a la local
+        let global_first = default_paths(&local, Some(&config_dir), true)
+            .into_iter()
+            .find(|p| p.exists());
+        assert_eq!(global_first, Some(user));
+
+        std::fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn aliases_must_be_unique_across_groups() {
+        let config: Config = serde_json::from_str(
+            r#"{ "groups": [
+                { "name": "Development", "aliases": ["dev"], "apps": [],
+                  "subgroups": [{ "name": "Docs", "aliases": ["dev"], "apps": [] }] },
+                { "name": "Music", "aliases": ["Docs"], "apps": [] }
+            ] }"#,
+        )
+        .unwrap();
+        let problems = config.validate().unwrap_err();
+        assert_eq!(
+            problems,
+            [
+                r#"group "Docs": alias "dev" is already used by group "Development""#,
+                r#"group "Music": alias "Docs" is the name of another group"#,
+            ]
+        );
+        assert!(config.groups[0].answers_to("DEV", true));
+        assert!(!config.groups[0].answers_to("DEV", false));
+    }
+
+    #[test]
+    fn group_color_tints_the_theme_unless_unknown() {
+        let theme = Theme::default();
+        let red = theme.tinted("red");
+        assert_eq!(red.border(), Color::Red);
+        assert_eq!(red.accent(), Color::Red);
+        assert_eq!(red.selected_bg(), Color::Red);
+        assert_eq!(red.selected_fg(), theme.selected_fg());
+        assert_eq!(theme.tinted("redish").border(), theme.border());
+
+        let config: Config =
+            serde_json::from_str(r#"{ "groups": [{ "name": "Prod", "color": "redish" }] }"#)
+                .unwrap();
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems, [r#"group #1 ("Prod"): unknown color "redish""#]);
+    }
+
+    #[test]
+    fn url_and_open_kinds_go_through_the_system_opener() {
+        let opts = LaunchOptions::default();
+        let url = app(r#"{ "name": "docs", "command": "https://docs.rs", "kind": "url" }"#);
+        let cmd = build_command(&url, &opts);
+        assert_eq!(cmd.get_program(), opener()[0].as_str());
+        assert_eq!(cmd.get_args().last(), Some(OsStr::new("https://docs.rs")));
+
+        let open = app(r#"{ "name": "notes", "command": "~/notes.md", "kind": "open" }"#);
+        let (_, args, _) = resolve_command(&open);
+        if let Some(home) = dirs::home_dir() {
+            assert_eq!(args.last().map(PathBuf::from), Some(home.join("notes.md")));
+        }
+
+        let config: Config = serde_json::from_str(
+            r#"{ "groups": [{ "name": "Web", "apps": [
+                { "name": "a", "command": "docs.rs", "kind": "url" },
+                { "name": "b", "command": "x.pdf", "kind": "open", "args": ["-n"] }
+            ] }] }"#,
+        )
+        .unwrap();
+        let problems = config.validate().unwrap_err();
+        assert_eq!(problems.len(), 2, "{:?}", problems);
+        assert!(problems[0].ends_with(r#"needs a URL with a scheme (https://...), got "docs.rs""#));
+        assert!(problems[1].starts_with(r#"group #1 ("Web"), app #2 ("b"): kind "open" opens"#));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn running_instances_finds_processes_by_program_name() {
+        let mut child = Command::new("sleep").arg("30").spawn().unwrap();
+        let found = running_instances(&[app(r#"{ "name": "nap", "command": "/bin/sleep" }"#)]);
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert!(found >= 1);
+        let missing = app(r#"{ "name": "x", "command": "no-such-program" }"#);
+        assert_eq!(running_instances(&[missing]), 0);
+    }
+
+    #[test]
+    fn shell_apps_are_matched_by_the_script_program_not_the_shell() {
+        // La app de Docker del launcher.json de ejemplo
+        let docker = app(
+            r#"{ "name": "Start Docker Containers", "command": "sh",
+                 "args": ["-c", "docker start $(docker ps -aq)"], "use_shell": true }"#,
+        );
+        assert_eq!(program_name(&docker).as_deref(), Some("docker"));
+        let assignment =
+            app(r#"{ "name": "a", "command": "", "use_shell": true, "args": ["X=1 run"] }"#);
+        assert_eq!(program_name(&assignment), None);
+        let url = app(r#"{ "name": "u", "command": "https://docs.rs", "kind": "url" }"#);
+        assert_eq!(program_name(&url), None);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn launch_workers_share_a_parallel_group_and_keep_the_order() {
+        let dir = temp_dir("workers");
+        // Cada app (`wait`) deja su marca y espera la de la otra: solo
+        // terminan bien si corren a la vez, y una tras otra la primera falla
+        let script = r#"touch "$1/$2"
+            for i in $(seq 100); do [ -e "$1/$3" ] && exit 0; sleep 0.05; done
+            exit 1"#;
+        let barrier = |name: &str, other: &str| {
+            format!(
+                r#"{{ "name": "{}", "command": "", "use_shell": true, "wait": true,
+                     "args": [{:?}, "sh", "{}", "{}", "{}"] }}"#,
+                name,
+                script,
+                dir.display(),
+                name,
+                other
+            )
+        };
+        let missing = r#"{ "name": "b", "command": "no-such-program" }"#;
+        let json = format!(
+            r#"{{ "name": "g", "apps": [{}, {}, {}] }}"#,
+            barrier("a", "c"),
+            missing,
+            barrier("c", "a")
+        );
+        let group: Group = serde_json::from_str(&json).unwrap();
+        let opts = LaunchOptions { launch_workers: 3, ..LaunchOptions::default() };
+        let mut results = launch_group_with(&group, &opts);
+        assert!(launch_succeeded(&mut results[0]), "a never saw c running");
+        assert!(results[1].as_ref().is_err_and(|e| e.to_string().contains("no-such-program")));
+        assert!(launch_succeeded(&mut results[2]), "c never saw a running");
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn restart_policy_and_backoff() {
+        let app = app(r#"{ "name": "a", "command": "x", "restart": "on-failure" }"#);
+        assert!(app.restart.restarts(Some(1)));
+        assert!(app.restart.restarts(None));
+        assert!(!app.restart.restarts(Some(0)));
+        assert!(RestartPolicy::Always.restarts(Some(0)));
+        assert!(!RestartPolicy::Never.restarts(Some(1)));
+        let delays: Vec<u64> = (0..7).map(|n| restart_delay(n).as_secs()).collect();
+        assert_eq!(delays, [1, 2, 4, 8, 16, 30, 30]);
+        assert_eq!(restart_delay(u32::MAX), Duration::from_secs(30));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn output_tail_keeps_the_last_lines() {
+        let mut child = Command::new("seq")
+            .args(["1", "30"])
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        let tail = OutputTail::capture(&mut child).unwrap();
+        child.wait().unwrap();
+        tail.finish();
+        let expected: Vec<String> = (11..=30).map(|i| i.to_string()).collect();
+        assert_eq!(tail.lines(), expected);
+    }
+
+    #[test]
+    fn build_command_passes_args_and_cwd() {
+        let app = app(r#"{ "name": "a", "command": "echo", "args": ["a b", "c"], "cwd": "/tmp" }"#);
+        let cmd = build_command(&app, &LaunchOptions::default());
+        assert_eq!(cmd.get_program(), "echo");
+        assert_eq!(args(&cmd), ["a b", "c"]);
+        assert_eq!(cmd.get_current_dir(), Some(Path::new("/tmp")));
+    }
+
+    #[test]
+    fn build_command_expands_env_vars_without_shell() {
+        let lookup = |name: &str| (name == "BUILD").then(|| "value".to_string());
+        let mut unset = Vec::new();
+        let expanded = expand_vars("$BUILD/x ${BUILD}y $ $NOPE", &mut unset, lookup);
+        assert_eq!(expanded, "value/x valuey $ ");
+        assert_eq!(unset, ["NOPE"]);
+
+        // Sin definir en el proceso: queda vacía
+        let app = app(r#"{ "name": "a", "command": "echo", "args": ["$LAUNCHER_TEST_UNSET/x"] }"#);
+        let cmd = build_command(&app, &LaunchOptions::default());
+        assert_eq!(args(&cmd), ["/x"]);
+    }
+
+    #[test]
+    fn file_args_are_read_at_launch_and_kept_out_of_the_preview() {
+        let dir = temp_dir("file-args");
+        let token = dir.join("token");
+        std::fs::write(&token, "s3cr$et\n").unwrap();
+        let json = format!(
+            r#"{{ "name": "a", "command": "curl", "args": ["-H", {{ "file": "{}" }}] }}"#,
+            token.display()
+        );
+        let app = app(&json);
+        let opts = LaunchOptions::default();
+        let line = format_command(&app, &opts);
+        assert_eq!(line, format!("curl -H \"$(cat {})\"", shell_quote(&token.to_string_lossy())));
+
+        let read = AppCommand {
+            args: app.args.iter().map(|a| a.read().unwrap()).collect(),
+            ..app
+        };
+        assert_eq!(args(&build_command(&read, &opts)), ["-H", "s3cr$et"]);
+
+        std::fs::remove_file(&token).unwrap();
+        let err = read.args[1].read().unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+        assert!(err.to_string().starts_with("cannot read argument file"));
+
+        // La vista previa muestra una ruta con espacios como un comando válido
+        let spaced = Arg::File { file: "my tokens/gh".to_string(), contents: None };
+        assert_eq!(spaced.to_string(), "\"$(cat 'my tokens/gh')\"");
+    }
+
+    #[test]
+    fn build_command_splits_the_command_line() {
+        let split = app(
+            r#"{ "name": "a", "command": "git commit -m 'two words'", "args": ["--quiet"],
+                 "split": true }"#,
+        );
+        let cmd = build_command(&split, &LaunchOptions::default());
+        assert_eq!(cmd.get_program(), "git");
+        assert_eq!(args(&cmd), ["commit", "-m", "two words", "--quiet"]);
+
+        // Sin `split` el comando entero es el programa
+        let plain = app(r#"{ "name": "a", "command": "git pull" }"#);
+        assert_eq!(build_command(&plain, &LaunchOptions::default()).get_program(), "git pull");
+    }
+
+    #[test]
+    fn build_command_uses_the_shell_flag_for_each_shell() {
+        let opts = LaunchOptions::default();
+        for (shell, flag) in [("sh", "-c"), ("fish", "-c"), ("cmd", "/C"), ("pwsh", "-Command")] {
+            let json = format!(
+                r#"{{ "name": "a", "command": "", "use_shell": true, "shell": "{}",
+                      "args": ["x"] }}"#,
+                shell
+            );
+            let cmd = build_command(&app(&json), &opts);
+            assert_eq!(cmd.get_program(), shell);
+            assert_eq!(args(&cmd), [flag, "x"]);
+        }
+    }
+
+    #[test]
+    fn build_command_prefers_the_app_shell_over_the_global_one() {
+        let opts = LaunchOptions {
+            shell: Some("bash".to_string()),
+            ..LaunchOptions::default()
+        };
+        let global = app(r#"{ "name": "a", "command": "", "use_shell": true, "args": ["x"] }"#);
+        assert_eq!(build_command(&global, &opts).get_program(), "bash");
+        let own = app(r#"{ "name": "a", "command": "", "use_shell": true, "shell": "zsh" }"#);
+        assert_eq!(build_command(&own, &opts).get_program(), "zsh");
+    }
+
+    #[test]
+    fn build_command_wraps_the_whole_shell_with_run_as() {
+        let app = app(
+            r#"{ "name": "a", "command": "", "use_shell": true, "shell": "sh",
+                 "args": ["echo hi | wc"], "run_as": "bob" }"#,
+        );
+        let cmd = build_command(&app, &LaunchOptions::default());
+        assert_eq!(cmd.get_program(), "sudo");
+        assert_eq!(args(&cmd), ["-n", "-u", "bob", "sh", "-c", "echo hi | wc"]);
+
+        let opts = LaunchOptions {
+            run_as_program: "doas".to_string(),
+            ..LaunchOptions::default()
+        };
+        assert_eq!(build_command(&app, &opts).get_program(), "doas");
+    }
+}
@@ -7,243 +7,3433 @@
 use ratatui::{
     backend::CrosstermBackend,
     crossterm::{
-        event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode},
+        event::{
+            self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyModifiers,
+            MouseButton, MouseEvent, MouseEventKind,
+        },
+        cursor::Show,
         execute,
         terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
     },
-    layout::{Alignment, Constraint, Direction, Layout},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Padding, Paragraph, Tabs, Wrap},
     Terminal,
 };
+use fuzzy_matcher::skim::SkimMatcherV2;
+use fuzzy_matcher::FuzzyMatcher;
+use launcher::{
+    execute_command, expand_path, flatten_groups, group_commands, launch_group_progress,
+    launch_group_with, launch_succeeded, missing_binary, relaunch_app, restart_delay,
+    running_instances, summarize_launch, terminate, Action, AppCommand, AppKind, Arg, Config,
+    Group, Keybindings, LaunchOptions, OutputTail, Prompt, Theme, RESTART_BACKOFF_RESET,
+};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
 use serde::{Deserialize, Serialize};
-use std::io;
-use std::path::PathBuf;
-use std::process::Command;
+use std::collections::{HashMap, VecDeque};
+use std::io::{self, IsTerminal};
+use std::path::{Path, PathBuf};
+use std::process::Child;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
 
 // ============================================================================
-// STRUCTS - Configuración y App
+// STRUCTS - App
 // ============================================================================
 
-/// Representa una aplicación dentro de un grupo
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AppCommand {
-    /// Nombre mostrado en la UI
-    pub name: String,
-    /// Comando a ejecutar
-    pub command: String,
-    /// Argumentos opcionales (ej: ["-c", "docker start $(docker ps -aq)"])
+/// Estado guardado entre sesiones en `<config_dir>/launcher/state.json`
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SavedState {
+    /// Nombre del último grupo resaltado (del nivel raíz)
+    #[serde(default)]
+    last: Option<String>,
+    /// Último lanzamiento de cada grupo (por `group_key`), en segundos Unix
     #[serde(default)]
-    pub args: Vec<String>,
-    /// Si true, usa `sh -c` para ejecutar (para comandos complejos)
+    launched: HashMap<String, i64>,
+    /// Últimos grupos lanzados (por `group_key`), el más reciente primero
     #[serde(default)]
-    pub use_shell: bool,
+    recent: Vec<String>,
 }
 
-/// Un grupo de aplicaciones a ejecutar juntas
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Group {
-    pub name: String,
-    pub apps: Vec<AppCommand>,
+/// Cantidad de grupos que se recuerdan en la sección "Recent"
+const RECENT_LIMIT: usize = 5;
+
+impl SavedState {
+    fn path() -> Option<PathBuf> {
+        dirs::config_dir().map(|d| d.join("launcher").join("state.json"))
+    }
+
+    /// Leer el estado guardado; si no existe o no se puede leer se empieza de cero
+    fn load() -> Self {
+        Self::path()
+            .and_then(|path| std::fs::read_to_string(path).ok())
+            .and_then(|contents| serde_json::from_str(&contents).ok())
+            .unwrap_or_default()
+    }
+
+    /// Guardar el estado. Un fallo no impide salir, se ignora
+    fn save(&self) {
+        let Some(path) = Self::path() else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(contents) = serde_json::to_string_pretty(self) {
+            let _ = std::fs::write(path, contents);
+        }
+    }
 }
 
-/// Configuración general del launcher
-#[derive(Debug, Serialize, Deserialize)]
-pub struct Config {
-    pub groups: Vec<Group>,
+/// Tiempo sin cambios nuevos antes de recargar: muchos editores guardan en
+/// varios pasos y así un solo guardado recarga una sola vez
+const WATCH_DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// Observador del archivo de configuración y de conf.d (`--watch`)
+struct ConfigWatcher {
+    /// Se guarda solo para que la observación siga activa
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<notify::Event>>,
+    /// Archivo de configuración observado
+    file: Option<PathBuf>,
+    /// Directorios cuyos archivos cuentan como configuración (conf.d)
+    dirs: Vec<PathBuf>,
+    /// Momento del último cambio que todavía no se recargó
+    changed_at: Option<Instant>,
 }
 
-impl Config {
-    /// Cargar configuración desde un archivo JSON
-    pub fn load(path: &PathBuf) -> io::Result<Self> {
-        let contents = std::fs::read_to_string(path)?;
-        serde_json::from_str(&contents)
-            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+impl ConfigWatcher {
+    /// Observar `config_path` y, si `conf_d`, también el directorio conf.d.
+    /// Se observa el directorio del archivo porque muchos editores lo
+    /// reemplazan al guardar en lugar de escribirlo
+    fn new(config_path: &Path, conf_d: bool) -> notify::Result<Self> {
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(tx)?;
+        // Rutas absolutas, que son las que llegan en los eventos
+        let canonical = |p: &Path| std::fs::canonicalize(p).unwrap_or_else(|_| p.to_path_buf());
+
+        let mut file = None;
+        let mut dirs = Vec::new();
+        if config_path.is_dir() {
+            dirs.push(canonical(config_path));
+        } else {
+            let path = canonical(config_path);
+            if let Some(parent) = path.parent() {
+                watcher.watch(parent, RecursiveMode::NonRecursive)?;
+            }
+            file = Some(path);
+        }
+        if let Some(dir) = launcher::conf_d_dir().filter(|d| conf_d && d.is_dir()) {
+            let dir = canonical(&dir);
+            if !dirs.contains(&dir) {
+                dirs.push(dir);
+            }
+        }
+        for dir in &dirs {
+            watcher.watch(dir, RecursiveMode::NonRecursive)?;
+        }
+
+        Ok(ConfigWatcher {
+            _watcher: watcher,
+            events,
+            file,
+            dirs,
+            changed_at: None,
+        })
     }
 
-    /// Cargar configuración desde ubicación estándar
-    /// 1. `./launcher.json`
-    /// 2. `~/.config/launcher/config.json`
-    /// 3. `/etc/launcher/config.json`
-    pub fn load_default() -> io::Result<Self> {
-        let paths = [
-            PathBuf::from("./launcher.json"),
-            dirs::config_dir()
-                .map(|d| d.join("launcher").join("config.json"))
-                .unwrap_or_default(),
-            PathBuf::from("/etc/launcher/config.json"),
-        ];
+    /// Procesar los eventos pendientes. Devuelve true cuando hubo cambios y
+    /// ya pasó `WATCH_DEBOUNCE` desde el último
+    fn changed(&mut self) -> bool {
+        while let Ok(event) = self.events.try_recv() {
+            let Ok(event) = event else {
+                continue;
+            };
+            if matches!(event.kind, EventKind::Access(_)) {
+                continue;
+            }
+            if event.paths.iter().any(|p| self.watches(p)) {
+                self.changed_at = Some(Instant::now());
+            }
+        }
+        if self.changed_at.is_some_and(|at| at.elapsed() >= WATCH_DEBOUNCE) {
+            self.changed_at = None;
+            return true;
+        }
+        false
+    }
+
+    /// Si un cambio en `path` afecta a la configuración
+    fn watches(&self, path: &Path) -> bool {
+        self.file.as_deref() == Some(path)
+            || path.parent().is_some_and(|parent| self.dirs.iter().any(|d| d == parent))
+    }
+}
+
+/// Duración de un mensaje de estado normal en el footer
+const STATUS_DURATION: Duration = Duration::from_secs(2);
+/// Los errores se muestran durante más tiempo
+const ERROR_STATUS_DURATION: Duration = Duration::from_secs(5);
+
+/// Entradas que se guardan en el registro de la sesión (`m`)
+const MESSAGE_LOG_LINES: usize = 500;
+/// Alto del panel del registro, bordes incluidos
+const MESSAGE_PANE_HEIGHT: u16 = 8;
+
+/// Mensaje temporal mostrado en el footer
+struct StatusMessage {
+    text: String,
+    error: bool,
+    since: Instant,
+}
+
+impl StatusMessage {
+    fn new(text: String, error: bool) -> Self {
+        StatusMessage {
+            text,
+            error,
+            since: Instant::now(),
+        }
+    }
+
+    /// Indica si el mensaje ya debe dejar de mostrarse
+    fn expired(&self) -> bool {
+        let ttl = if self.error {
+            ERROR_STATUS_DURATION
+        } else {
+            STATUS_DURATION
+        };
+        self.since.elapsed() >= ttl
+    }
+}
+
+/// Evento enviado por el hilo que lanza un grupo
+enum LaunchEvent {
+    /// El grupo terminó de lanzarse
+    Finished {
+        /// Clave del grupo (ver `App::group_key`)
+        key: String,
+        summary: String,
+        error: bool,
+        /// Procesos lanzados (o el error de cada app), en el orden del grupo
+        apps: Vec<TrackedApp>,
+    },
+    /// Terminó "lanzar todo"; llega después del `Finished` de cada grupo
+    AllFinished { summary: String, error: bool },
+    /// Una app del grupo `key` está por lanzarse
+    Progress { key: String, text: String },
+    /// Procesos ya en marcha con los programas del grupo `key` (`check_running`)
+    Running { key: String, count: usize },
+    /// Terminó el reinicio de la app con ese `TrackedApp::id`
+    Restarted { id: usize, res: io::Result<Child> },
+}
+
+/// Avisar al hilo de la interfaz que se lanza la app `i` de `total`
+fn report_progress(tx: &Sender<LaunchEvent>, key: &str, total: usize, i: usize, app: &AppCommand) {
+    let _ = tx.send(LaunchEvent::Progress {
+        key: key.to_string(),
+        text: format!("Launching app {}/{}: {}...", i + 1, total, app.name),
+    });
+}
+
+/// Fotogramas del indicador de actividad mientras un grupo se lanza
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Progreso del lanzamiento en curso, mostrado en el footer con el spinner
+struct Progress {
+    key: String,
+    text: String,
+    since: Instant,
+}
+
+impl Progress {
+    /// Fotograma del spinner según el tiempo transcurrido
+    fn spinner(&self) -> &'static str {
+        SPINNER[(self.since.elapsed().as_millis() / 100) as usize % SPINNER.len()]
+    }
+}
+
+/// Evento de fin de lanzamiento de un grupo, con su resumen y sus procesos
+fn finished_event(key: String, group: &Group, results: Vec<io::Result<Child>>) -> LaunchEvent {
+    let error = results.iter().any(|r| r.is_err());
+    let summary = summarize_launch(group, &results);
+    let apps = group
+        .apps
+        .iter()
+        .zip(results)
+        .map(|(app, res)| TrackedApp::new(group, app, res))
+        .collect();
+    LaunchEvent::Finished {
+        key,
+        summary,
+        error,
+        apps,
+    }
+}
+
+/// Estado de una app lanzada en esta sesión
+#[derive(Debug, Clone, PartialEq)]
+enum AppStatus {
+    Running,
+    /// Terminó; `None` si fue por una señal
+    Exited(Option<i32>),
+    /// No se pudo lanzar (o no se pudo consultar su estado)
+    Failed(String),
+}
+
+/// Siguiente `TrackedApp::id` libre
+static NEXT_TRACKED_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// App lanzada cuyo proceso se sigue con `try_wait`
+struct TrackedApp {
+    /// Único en la sesión, para encontrarla cuando termina su reinicio
+    id: usize,
+    name: String,
+    /// Su grupo con ella como única app (ver `Group::with_only`), para
+    /// reiniciarla según su `restart` como se lanzó
+    group: Group,
+    child: Option<Child>,
+    status: AppStatus,
+    /// Últimas líneas de su salida, si se capturó (tareas `oneshot`)
+    output: Option<OutputTail>,
+    /// Veces que se volvió a lanzar por su `restart`, para mostrarlo
+    restarts: u32,
+    /// Reinicios seguidos sin quedar en marcha `RESTART_BACKOFF_RESET`: lo
+    /// que se duplica la espera del siguiente
+    backoff: u32,
+    /// Momento en que arrancó el proceso actual, si arrancó
+    started: Option<Instant>,
+    /// Momento del próximo reinicio, si hay uno pendiente
+    restart_at: Option<Instant>,
+    /// El reinicio se está haciendo en otro hilo
+    restarting: bool,
+    /// Detenida con `x`: ya no se reinicia
+    stopped: bool,
+}
+
+impl TrackedApp {
+    fn new(group: &Group, app: &AppCommand, res: io::Result<Child>) -> Self {
+        let (child, status) = match res {
+            Ok(child) => (Some(child), AppStatus::Running),
+            Err(e) => (None, AppStatus::Failed(e.to_string())),
+        };
+        let started = child.is_some().then(Instant::now);
+        TrackedApp {
+            id: NEXT_TRACKED_ID.fetch_add(1, Ordering::Relaxed),
+            name: app.name.clone(),
+            group: group.with_only(app),
+            child,
+            status,
+            output: None,
+            restarts: 0,
+            backoff: 0,
+            started,
+            restart_at: None,
+            restarting: false,
+            stopped: false,
+        }
+    }
+
+    /// La app tal como se lanzó
+    fn command(&self) -> &AppCommand {
+        &self.group.apps[0]
+    }
+
+    /// Programar el próximo reinicio si la app terminó y su `restart` lo pide.
+    /// Las tareas `oneshot`, `wait` e `interactive` no se reinician
+    fn schedule_restart(&mut self) {
+        let code = match &self.status {
+            AppStatus::Running => return,
+            AppStatus::Exited(code) => *code,
+            AppStatus::Failed(_) => None,
+        };
+        let app = self.command();
+        let supervised = !app.oneshot && !app.wait && !app.interactive;
+        if self.stopped || !supervised || !app.restart.restarts(code) {
+            return;
+        }
+        // Si estuvo en marcha un buen rato, no es un reinicio seguido
+        if self.started.take().is_some_and(|at| at.elapsed() >= RESTART_BACKOFF_RESET) {
+            self.backoff = 0;
+        }
+        self.restart_at = Some(Instant::now() + restart_delay(self.backoff));
+        self.backoff += 1;
+    }
+
+    /// Si ya pasó la espera de su reinicio, volver a lanzar la app en otro
+    /// hilo, como un lanzamiento normal (`wait_for`, `retries`, registro y
+    /// notificación). El resultado llega como `LaunchEvent::Restarted`
+    fn restart_if_due(&mut self, tx: &Sender<LaunchEvent>, opts: &LaunchOptions) {
+        if self.restarting || self.restart_at.is_none_or(|at| Instant::now() < at) {
+            return;
+        }
+        self.restart_at = None;
+        self.restarting = true;
+        self.restarts += 1;
+        let (tx, id, group, opts) = (tx.clone(), self.id, self.group.clone(), opts.clone());
+        std::thread::spawn(move || {
+            let res = relaunch_app(&group, &opts);
+            let _ = tx.send(LaunchEvent::Restarted { id, res });
+        });
+    }
+
+    /// Tomar el resultado de su reinicio. Devuelve la nota para el registro
+    /// de la sesión
+    fn restarted(&mut self, res: io::Result<Child>, log: Option<&OutputTail>) -> String {
+        self.restarting = false;
+        match res {
+            // Se detuvo con `x` mientras se reiniciaba
+            Ok(child) if self.stopped => {
+                let _ = terminate(child.id());
+                self.child = Some(child);
+                self.status = AppStatus::Running;
+                format!("{} stopped before its restart finished", self.name)
+            }
+            Ok(child) => {
+                self.child = Some(child);
+                self.status = AppStatus::Running;
+                self.started = Some(Instant::now());
+                self.capture(log);
+                format!("↻ {} restarted ({}×)", self.name, self.restarts)
+            }
+            Err(e) => {
+                let note = format!("{} failed to restart: {}", self.name, e);
+                self.child = None;
+                self.status = AppStatus::Failed(e.to_string());
+                self.schedule_restart();
+                note
+            }
+        }
+    }
+
+    /// Empezar a leer la salida capturada del proceso: a `log` con
+    /// `--attach`, o a un registro propio (tareas `oneshot`)
+    fn capture(&mut self, log: Option<&OutputTail>) {
+        let Some(child) = &mut self.child else {
+            return;
+        };
+        match log {
+            Some(log) => {
+                log.attach(child, &self.name);
+            }
+            None => self.output = OutputTail::capture(child),
+        }
+    }
+
+    /// Popup con la salida capturada, si la hay
+    fn output_popup(&self) -> Option<OutputPopup> {
+        let status = match &self.status {
+            AppStatus::Running => "running".to_string(),
+            AppStatus::Exited(Some(code)) => format!("exit {}", code),
+            AppStatus::Exited(None) => "killed".to_string(),
+            AppStatus::Failed(e) => e.clone(),
+        };
+        Some(OutputPopup {
+            title: format!(" Output of {} ({}) ", self.name, status),
+            output: self.output.clone()?,
+            scroll: 0,
+        })
+    }
+
+    /// Mensaje para el footer cuando termina una tarea `oneshot`
+    fn oneshot_result(&self) -> Option<StatusMessage> {
+        if !self.command().oneshot {
+            return None;
+        }
+        let (text, error) = match &self.status {
+            AppStatus::Running => return None,
+            AppStatus::Exited(Some(0)) => (format!("✓ {} finished", self.name), false),
+            AppStatus::Exited(Some(code)) => {
+                (format!("✗ {} failed (exit {})", self.name, code), true)
+            }
+            AppStatus::Exited(None) => (format!("✗ {} was killed", self.name), true),
+            AppStatus::Failed(e) => (format!("✗ {} failed: {}", self.name, e), true),
+        };
+        Some(StatusMessage::new(text, error))
+    }
+
+    /// Actualizar el estado sin bloquear. Devuelve true si cambió
+    fn poll(&mut self) -> bool {
+        if self.status != AppStatus::Running {
+            return false;
+        }
+        if let Some(child) = &mut self.child {
+            match child.try_wait() {
+                Ok(Some(status)) => self.status = AppStatus::Exited(status.code()),
+                Ok(None) => return false,
+                Err(e) => self.status = AppStatus::Failed(e.to_string()),
+            }
+            self.schedule_restart();
+            return true;
+        }
+        false
+    }
+}
+
+/// Popup desplazable con las últimas líneas de salida de una app
+struct OutputPopup {
+    title: String,
+    output: OutputTail,
+    /// Primera línea visible
+    scroll: u16,
+}
+
+/// Filtrar `names` con búsqueda difusa. Devuelve los índices que coinciden,
+/// de mejor a peor puntuación, y las posiciones coincidentes de cada uno
+fn fuzzy_filter(
+    matcher: &SkimMatcherV2,
+    names: &[String],
+    query: &str,
+) -> (Vec<usize>, Vec<Vec<usize>>) {
+    if query.is_empty() {
+        return ((0..names.len()).collect(), vec![Vec::new(); names.len()]);
+    }
+    let mut scored: Vec<(i64, usize, Vec<usize>)> = names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let (score, positions) = matcher.fuzzy_indices(name, query)?;
+            Some((score, i, positions))
+        })
+        .collect();
+    // Orden estable: a igual puntuación se mantiene el orden de la config
+    scored.sort_by_key(|(score, _, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, i, p)| (i, p)).unzip()
+}
+
+/// Paleta de comandos: todas las apps de todos los grupos en una lista filtrable
+struct Palette {
+    query: String,
+    /// Grupos lanzables con su clave (ver `flatten_groups`)
+    groups: Vec<(String, Group)>,
+    /// Índice en `groups` e índice de la app de cada entrada
+    entries: Vec<(usize, usize)>,
+    /// Texto `grupo / app` de cada entrada, sobre el que se busca
+    labels: Vec<String>,
+    /// Entradas que coinciden con `query`, y sus posiciones coincidentes
+    visible: Vec<usize>,
+    match_positions: Vec<Vec<usize>>,
+    list_state: ListState,
+}
 
-        for path in &paths {
-            if path.exists() {
-                return Self::load(path);
+impl Palette {
+    fn new(config: &Config) -> Self {
+        let groups = flatten_groups(&config.groups);
+        let mut entries = Vec::new();
+        let mut labels = Vec::new();
+        for (gi, (key, group)) in groups.iter().enumerate() {
+            // Los alias se muestran junto al grupo para poder buscar por ellos
+            let key = if group.aliases.is_empty() {
+                key.clone()
+            } else {
+                format!("{} ({})", key, group.aliases.join(", "))
+            };
+            for (ai, app) in group.apps.iter().enumerate() {
+                entries.push((gi, ai));
+                labels.push(format!("{} / {}", key, app.name));
             }
         }
+        let visible = (0..labels.len()).collect();
+        let match_positions = vec![Vec::new(); labels.len()];
+        Palette {
+            query: String::new(),
+            groups,
+            entries,
+            labels,
+            visible,
+            match_positions,
+            list_state: ListState::default().with_selected(Some(0)),
+        }
+    }
+
+    fn refilter(&mut self, matcher: &SkimMatcherV2) {
+        (self.visible, self.match_positions) = fuzzy_filter(matcher, &self.labels, &self.query);
+        let selected = if self.visible.is_empty() { None } else { Some(0) };
+        self.list_state.select(selected);
+    }
+
+    fn next(&mut self) {
+        if !self.visible.is_empty() {
+            let i = self.list_state.selected().map_or(0, |i| (i + 1) % self.visible.len());
+            self.list_state.select(Some(i));
+        }
+    }
+
+    fn prev(&mut self) {
+        if !self.visible.is_empty() {
+            let i = match self.list_state.selected() {
+                Some(0) | None => self.visible.len() - 1,
+                Some(i) => i - 1,
+            };
+            self.list_state.select(Some(i));
+        }
+    }
+
+    /// Clave, grupo e índice de la app de la entrada resaltada
+    fn selected(&self) -> Option<(String, Group, usize)> {
+        let entry = *self.visible.get(self.list_state.selected()?)?;
+        let (gi, ai) = self.entries[entry];
+        let (key, group) = &self.groups[gi];
+        Some((key.clone(), group.clone(), ai))
+    }
+}
+
+/// Formulario con las variables (`prompts`) de un grupo, una por vez,
+/// antes de lanzarlo
+struct PromptForm {
+    /// Clave del grupo (ver `App::group_key`)
+    key: String,
+    group: Group,
+    /// App a lanzar sola (índice en `group.apps`), o `None` para todo el grupo
+    app: Option<usize>,
+    /// Valores ya respondidos
+    values: HashMap<String, String>,
+    /// Texto escrito para la variable actual
+    input: String,
+}
+
+impl PromptForm {
+    fn new(key: String, group: Group, app: Option<usize>) -> Self {
+        PromptForm {
+            key,
+            group,
+            app,
+            values: HashMap::new(),
+            input: String::new(),
+        }
+    }
+
+    /// Variable que se está pidiendo
+    fn current(&self) -> Option<&Prompt> {
+        self.group.missing_prompt(&self.values)
+    }
 
-        Err(io::Error::new(
-            io::ErrorKind::NotFound,
-            "No config file found. Create launcher.json in current directory.",
-        ))
+    /// Guardar la respuesta actual (o su valor por defecto) y pasar a la
+    /// siguiente. Una variable obligatoria sin respuesta no avanza
+    fn submit(&mut self) {
+        let Some(prompt) = self.current().cloned() else {
+            return;
+        };
+        let value = match (self.input.is_empty(), prompt.default) {
+            (true, Some(default)) => default,
+            (true, None) => return,
+            (false, _) => std::mem::take(&mut self.input),
+        };
+        self.values.insert(prompt.name, value);
     }
 }
 
+/// Lanzamiento a la espera de que el usuario lo confirme
+enum PendingLaunch {
+    /// Grupo del nivel actual (por índice)
+    Group(usize),
+    /// Una sola app (índice `idx`) de un grupo, identificado por su clave
+    App { key: String, group: Box<Group>, idx: usize },
+    /// Todos los grupos
+    All,
+}
+
 /// Estructura principal de la aplicación
 struct App {
-    groups: Vec<String>,  // Nombres de grupos
+    groups: Vec<String>,  // Nombres de los grupos del nivel actual
+    /// Índices (en `groups`) de los grupos visibles tras aplicar el filtro
+    visible: Vec<usize>,
+    /// Posición seleccionada dentro de `visible`
     selected: usize,
+    /// Estado de la lista (selección y desplazamiento) para el render
+    list_state: ListState,
     config: Config,
+    /// Archivo del que se cargó la configuración
+    config_path: PathBuf,
+    /// Ruta pasada con `--config`; si es `None` al recargar se repite la búsqueda
+    /// estándar (incluyendo conf.d)
+    explicit_config: Option<PathBuf>,
+    /// Pila de navegación por subgrupos: índice de cada grupo abierto en su nivel
+    nav: Vec<usize>,
+    /// Grupo (índice en el nivel actual) cuyas apps se están listando para
+    /// lanzarlas de a una; mientras tanto `groups` contiene nombres de apps
+    expanded: Option<usize>,
+    /// Pestaña de categoría activa: 0 es "All", `n` es `categories()[n - 1]`
+    tab: usize,
+    /// Texto de búsqueda actual
+    query: String,
+    /// Si true, las teclas escritas se agregan a `query`
+    searching: bool,
+    /// Posiciones (en caracteres) que coinciden con la búsqueda, por cada
+    /// entrada de `visible`, para resaltarlas
+    match_positions: Vec<Vec<usize>>,
+    matcher: SkimMatcherV2,
+    /// Lanzamiento esperando confirmación (grupos con `confirm`)
+    pending: Option<PendingLaunch>,
+    /// Procesos ya en marcha del lanzamiento pendiente (`check_running`)
+    pending_running: usize,
+    /// Lanzamiento a la espera del recuento de procesos de `check_running`,
+    /// con la clave del grupo y si además pide confirmación
+    checking: Option<(PendingLaunch, String, bool)>,
+    /// Si true, se muestra el popup de ayuda con todas las teclas
+    show_help: bool,
+    /// Paleta de comandos abierta (`:` o Ctrl-P), si la hay
+    palette: Option<Palette>,
+    /// Variables que se están pidiendo antes de lanzar un grupo
+    prompt_form: Option<PromptForm>,
+    /// Salida capturada de una tarea `oneshot`, abierta al fallar o con `o`
+    output_popup: Option<OutputPopup>,
+    /// App que se está lanzando, mostrada en el footer hasta que termine el grupo
+    progress: Option<Progress>,
+    /// Resumen del último lanzamiento, mostrado temporalmente en el footer
+    last_result: Option<StatusMessage>,
+    /// Registro de la sesión: los mensajes del footer, avisos de la
+    /// configuración y reinicios o fines de las apps, con su hora
+    messages: VecDeque<(Instant, String)>,
+    /// Mostrar el registro en un panel abajo (`m`)
+    show_messages: bool,
+    /// Entradas que el panel del registro está desplazado hacia atrás
+    messages_scroll: usize,
+    /// Si true, los lanzamientos solo registran el comando en `dry_run_log`
+    dry_run: bool,
+    /// Comandos que se habrían ejecutado en modo dry-run
+    dry_run_log: Vec<String>,
+    /// Salida combinada de las apps lanzadas con `--attach`
+    attach_log: Option<OutputTail>,
+    /// Líneas del log de `--attach` (o del popup de salida) ya dibujadas
+    output_seen: usize,
+    /// Apps lanzadas por cada grupo (por `group_key`), para mostrar su estado
+    /// y poder detenerlas
+    processes: HashMap<String, Vec<TrackedApp>>,
+    /// Canal por el que los hilos de lanzamiento informan su resultado
+    events_tx: Sender<LaunchEvent>,
+    events_rx: Receiver<LaunchEvent>,
+    /// Área de la lista en el último dibujado, para traducir clics a filas
+    list_area: Rect,
+    /// Columnas en las que se repartió la lista en el último dibujado (1 si
+    /// los grupos caben en una), filas de cada una y primera fila visible
+    list_columns: usize,
+    column_rows: usize,
+    column_offset: usize,
+    /// Fila y momento del último clic, para detectar doble clic
+    last_click: Option<(usize, Instant)>,
+    /// Momento del último lanzamiento pedido, para ignorar Enter repetidos
+    last_launch: Option<Instant>,
+    /// Apps `interactive` (con la clave de su grupo, y el grupo reducido a
+    /// ellas) que esperan a correr en primer plano con la TUI suspendida
+    foreground: VecDeque<(String, Group)>,
+    /// Apps `interactive` de un grupo que todavía se está lanzando: pasan a
+    /// `foreground` cuando el resto del grupo y sus hooks terminan
+    after_launch: HashMap<String, Vec<Group>>,
+    /// Observador de la configuración con `--watch`
+    watcher: Option<ConfigWatcher>,
+    /// Momento del último lanzamiento de cada grupo, guardado entre sesiones
+    launched: HashMap<String, i64>,
+    /// Últimos grupos lanzados, el más reciente primero (ver `SavedState`)
+    recent: Vec<String>,
+    /// Entrada de "Recent" resaltada; mientras es `Some` la lista no tiene foco
+    recent_focus: Option<usize>,
+    /// Hilos de lanzamiento que pueden seguir en curso
+    launches: Vec<JoinHandle<()>>,
+    /// Lanzar y salir (`L`): intención pendiente mientras se confirma
+    quit_after_launch: bool,
+    /// Se lanzó con `L`: salir cuando terminen los lanzamientos
+    quit_when_done: bool,
+    /// Se pidió salir con apps todavía en marcha: esperando confirmación
+    confirm_quit: bool,
+    /// Dibujar sin colores, solo con atributos (ver `strip_colors`)
+    no_color: bool,
+    /// Al recargar sin `--config`, buscar la configuración global antes que la local
+    prefer_global: bool,
 }
 
+/// Tiempo máximo entre dos clics en la misma fila para contar como doble clic
+const DOUBLE_CLICK: Duration = Duration::from_millis(400);
+
+/// Tiempo durante el que se ignora un nuevo lanzamiento tras el anterior, para
+/// que mantener o repetir Enter no arranque dos copias de las mismas apps
+const LAUNCH_COOLDOWN: Duration = Duration::from_millis(500);
+
 impl App {
     /// Crear nueva instancia desde configuración
-    fn new(config: Config) -> Self {
+    fn new(config: Config, config_path: PathBuf, explicit_config: Option<PathBuf>) -> Self {
         // Extraer solo los nombres de los grupos para la UI
-        let groups = config.groups.iter().map(|g| g.name.clone()).collect();
+        let groups: Vec<String> = config.groups.iter().map(|g| g.name.clone()).collect();
+        let visible = (0..groups.len()).collect();
+        let match_positions = vec![Vec::new(); groups.len()];
+        let (events_tx, events_rx) = mpsc::channel();
+        let messages = config
+            .warnings
+            .iter()
+            .map(|w| (Instant::now(), format!("Warning: {}", w)))
+            .collect();
         App {
             groups,
+            visible,
             selected: 0,
+            list_state: ListState::default().with_selected(Some(0)),
             config,
+            config_path,
+            explicit_config,
+            nav: Vec::new(),
+            expanded: None,
+            tab: 0,
+            query: String::new(),
+            searching: false,
+            match_positions,
+            matcher: SkimMatcherV2::default(),
+            pending: None,
+            pending_running: 0,
+            checking: None,
+            show_help: false,
+            palette: None,
+            prompt_form: None,
+            output_popup: None,
+            progress: None,
+            last_result: None,
+            messages,
+            show_messages: false,
+            messages_scroll: 0,
+            dry_run: false,
+            dry_run_log: Vec::new(),
+            attach_log: None,
+            output_seen: 0,
+            processes: HashMap::new(),
+            events_tx,
+            events_rx,
+            list_area: Rect::default(),
+            list_columns: 1,
+            column_rows: 0,
+            column_offset: 0,
+            last_click: None,
+            last_launch: None,
+            foreground: VecDeque::new(),
+            after_launch: HashMap::new(),
+            watcher: None,
+            launched: HashMap::new(),
+            recent: Vec::new(),
+            recent_focus: None,
+            launches: Vec::new(),
+            quit_after_launch: false,
+            quit_when_done: false,
+            confirm_quit: false,
+            no_color: false,
+            prefer_global: false,
         }
     }
 
     fn next(&mut self) {
-        self.selected = (self.selected + 1) % self.groups.len();
+        // Desde la última entrada de "Recent" se baja al principio de la lista
+        if let Some(i) = self.recent_focus {
+            if i + 1 < self.shown_recent().len() {
+                self.recent_focus = Some(i + 1);
+            } else {
+                self.first();
+            }
+            return;
+        }
+        // Con un filtro sin coincidencias no hay nada que recorrer
+        if self.visible.is_empty() {
+            return;
+        }
+        self.selected = if self.selected + 1 < self.visible.len() {
+            self.selected + 1
+        } else if self.config.wrap_navigation {
+            0
+        } else {
+            self.selected
+        };
+        self.sync_list_state();
     }
 
     fn prev(&mut self) {
+        if let Some(i) = self.recent_focus {
+            self.recent_focus = Some(i.saturating_sub(1));
+            return;
+        }
+        // Desde el principio de la lista se sube a "Recent", si se muestra
+        let recent = self.shown_recent().len();
+        if self.selected == 0 && recent > 0 {
+            self.recent_focus = Some(recent - 1);
+            return;
+        }
+        if self.visible.is_empty() {
+            return;
+        }
         if self.selected > 0 {
             self.selected -= 1;
-        } else {
-            self.selected = self.groups.len().saturating_sub(1);
+        } else if self.config.wrap_navigation {
+            self.selected = self.visible.len().saturating_sub(1);
         }
+        self.sync_list_state();
     }
 
-    /// Ejecutar todas las aplicaciones del grupo seleccionado
-    fn select(&self) {
-        // Verificar que selected es válido
-        if self.selected >= self.config.groups.len() {
+    /// Saltar al primer elemento de la lista
+    fn first(&mut self) {
+        self.selected = 0;
+        self.sync_list_state();
+    }
+
+    /// Saltar al último elemento de la lista
+    fn last(&mut self) {
+        self.selected = self.visible.len().saturating_sub(1);
+        self.sync_list_state();
+    }
+
+    /// Avanzar (o retroceder) una pantalla, según la altura de la lista en el
+    /// último dibujado. No da la vuelta: se detiene en los extremos
+    fn page(&mut self, down: bool) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let rows = (self.list_area.height.saturating_sub(2) as usize).max(1);
+        self.selected = if down {
+            (self.selected + rows).min(self.visible.len() - 1)
+        } else {
+            self.selected.saturating_sub(rows)
+        };
+        self.sync_list_state();
+    }
+
+    /// Pasar a la columna de al lado (`h`/`l`) manteniendo la fila, si la
+    /// lista está repartida en columnas. En una columna más corta se queda
+    /// en su último elemento
+    fn move_column(&mut self, right: bool) {
+        if self.list_columns < 2 || self.recent_focus.is_some() || self.column_rows == 0 {
+            return;
+        }
+        let rows = self.column_rows;
+        let columns = self.visible.len().div_ceil(rows);
+        let column = self.selected / rows;
+        if right && column + 1 < columns {
+            self.selected = (self.selected + rows).min(self.visible.len() - 1);
+        } else if !right && column > 0 {
+            self.selected -= rows;
+        } else {
             return;
         }
+        self.sync_list_state();
+    }
 
-        // Acceder al grupo seleccionado desde la configuración        
-        let group = &self.config.groups[self.selected];
-        // Ejecutar cada aplicación del grupo (Esto evita problemas de borrow)
-        for app in &group.apps {
-            Self::execute_command(app);
+    /// Grupos recientes a mostrar sobre la lista: solo en el nivel raíz, sin
+    /// búsqueda, y omitiendo los que ya no existen en la configuración
+    fn shown_recent(&self) -> Vec<&str> {
+        let root = self.nav.is_empty() && self.expanded.is_none();
+        if !root || self.searching || !self.query.is_empty() {
+            return Vec::new();
         }
+        self.recent
+            .iter()
+            .filter(|key| self.resolve_key(key).is_some())
+            .map(String::as_str)
+            .collect()
     }
 
-    /// Ejecutar un comando individual de forma desacoplada de la terminal
-    fn execute_command(app: &AppCommand) {
-        let child = if app.use_shell {
-            // Para comandos complejos con pipes, variables, etc
-            Command::new("sh")
-                .arg("-c")
-                .args(&app.args)
-                .spawn()
-        } else {
-            // Para comandos simples
-            let mut cmd = Command::new(&app.command);
-            cmd.args(&app.args);
-            
-            // Importante: desacoplar del padre para que la app no muera
-            // cuando cierre la terminal
-            // Esto crea una nueva sesión de proceso con setsid()
-            #[cfg(unix)]
-            {
-                use std::os::unix::process::CommandExt;
-                unsafe {
-                    cmd.pre_exec(|| {
-                        // Cambiar a nuevo session group
-                        libc::setsid();
-                        Ok(())
-                    });
-                }
-            }
-            cmd.spawn()
+    /// Índices desde la raíz del grupo con esa clave (`Dev › Web`)
+    fn resolve_key(&self, key: &str) -> Option<Vec<usize>> {
+        let mut groups = &self.config.groups[..];
+        let mut path = Vec::new();
+        for name in key.split(" › ") {
+            let idx = groups.iter().position(|g| g.name == name)?;
+            path.push(idx);
+            groups = &groups[idx].subgroups;
+        }
+        Some(path)
+    }
+
+    /// Registrar un lanzamiento en "Recent": sin repetidos, el último primero
+    fn remember_recent(&mut self, key: &str) {
+        self.recent.retain(|k| k != key);
+        self.recent.insert(0, key.to_string());
+        self.recent.truncate(RECENT_LIMIT);
+    }
+
+    /// Lanzar la entrada resaltada de "Recent". Se navega hasta el nivel del
+    /// grupo para que se lance (y confirme) igual que si se eligiera en la lista
+    fn launch_recent(&mut self, i: usize) {
+        let Some(path) = self.shown_recent().get(i).and_then(|key| self.resolve_key(key)) else {
+            return;
         };
+        let (&idx, parents) = path.split_last().expect("keys are never empty");
+        self.tab = 0;
+        self.nav = parents.to_vec();
+        self.load_level(0);
+        if let Some(pos) = self.visible.iter().position(|&v| v == idx) {
+            self.selected = pos;
+            self.sync_list_state();
+            self.select();
+        }
+    }
 
-        if let Err(e) = child {
-            eprintln!("Error al ejecutar {}: {}", app.name, e);
+    /// Reflejar `selected` en el `ListState` usado para dibujar.
+    /// Ratatui ajusta el desplazamiento para que la selección quede a la vista.
+    /// Mover la selección de la lista le devuelve el foco
+    fn sync_list_state(&mut self) {
+        self.recent_focus = None;
+        if self.visible.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state.select(Some(self.selected));
         }
     }
-}
 
-// ============================================================================
-// FUNCIÓN main
-// ============================================================================
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Cargar configuración
-    let config = Config::load_default()?;
+    /// Índice (en el nivel actual) del grupo actualmente resaltado
+    fn selected_group(&self) -> Option<usize> {
+        self.visible.get(self.selected).copied()
+    }
 
-    if config.groups.is_empty() {
-        eprintln!("Error: No groups configured in config file");
-        return Ok(());
+    /// Tema de la interfaz teñido con el `color` del grupo seleccionado o, si
+    /// no tiene, del grupo abierto más cercano que lo tenga
+    fn theme(&self) -> Theme {
+        let mut groups = &self.config.groups[..];
+        let mut colors = Vec::new();
+        for &idx in &self.nav {
+            colors.push(groups[idx].color.as_deref());
+            groups = &groups[idx].subgroups;
+        }
+        colors.push(self.selected_group().and_then(|i| groups[i].color.as_deref()));
+        match colors.into_iter().rev().flatten().next() {
+            Some(color) => self.config.theme.tinted(color),
+            None => self.config.theme.clone(),
+        }
     }
 
-    enable_raw_mode()?;
-    let mut stdout = io::stdout();
-    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    /// Grupos del nivel de navegación actual
+    fn current_groups(&self) -> &[Group] {
+        let mut groups = &self.config.groups[..];
+        for &idx in &self.nav {
+            groups = &groups[idx].subgroups;
+        }
+        groups
+    }
 
-    let backend = CrosstermBackend::new(stdout);
-    let mut terminal = Terminal::new(backend)?;
+    /// Nombres de los grupos abiertos, para mostrar como breadcrumbs
+    fn breadcrumbs(&self) -> Vec<&str> {
+        let mut groups = &self.config.groups[..];
+        let mut names = Vec::new();
+        for &idx in &self.nav {
+            names.push(groups[idx].name.as_str());
+            groups = &groups[idx].subgroups;
+        }
+        names
+    }
 
-    let app = App::new(config);
-    let res = run_app(&mut terminal, app);
+    /// Reconstruir la lista visible después de cambiar de nivel
+    fn load_level(&mut self, selected: usize) {
+        let names = self.current_groups().iter().map(|g| g.name.clone()).collect();
+        self.show_names(names, selected);
+    }
 
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
-        LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+    /// Reemplazar los elementos de la lista, limpiando la búsqueda
+    fn show_names(&mut self, names: Vec<String>, selected: usize) {
+        self.groups = names;
+        self.query.clear();
+        self.searching = false;
+        self.selected = selected;
+        self.refilter();
+    }
 
-    if let Err(err) = res {
-        eprintln!("Error: {}", err);
+    /// Entrar en los subgrupos del grupo `idx` del nivel actual
+    fn descend(&mut self, idx: usize) {
+        self.nav.push(idx);
+        self.load_level(0);
     }
 
-    Ok(())
-}
+    /// Mostrar las apps del grupo resaltado para lanzar solo una.
+    /// Los grupos con subgrupos se abren igual que con Enter
+    fn expand(&mut self) {
+        if self.expanded.is_some() {
+            return;
+        }
+        let Some(idx) = self.selected_group() else {
+            return;
+        };
+        let group = &self.current_groups()[idx];
+        if !group.enabled || !group.subgroups.is_empty() {
+            self.select();
+            return;
+        }
+        if group.apps.is_empty() {
+            return;
+        }
+        let names = group.apps.iter().map(|a| a.name.clone()).collect();
+        self.expanded = Some(idx);
+        self.show_names(names, 0);
+    }
 
-// ============================================================================
-// FUNCIÓN run_app
-// ============================================================================
-fn run_app(
-    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
-    mut app: App,
-) -> io::Result<()> {
-    loop {
-        terminal.draw(|f| ui(f, &app))?;
+    /// Volver al nivel anterior, dejando seleccionado el grupo del que se salió
+    fn ascend(&mut self) {
+        if let Some(idx) = self.expanded.take() {
+            self.load_level(idx);
+        } else if let Some(idx) = self.nav.pop() {
+            self.load_level(idx);
+        }
+    }
 
-        if crossterm::event::poll(std::time::Duration::from_millis(250))? {
-            if let Event::Key(key) = event::read()? {
-                match key.code {
-                    KeyCode::Char('q') | KeyCode::Esc => {
-                        return Ok(());
-                    }
-                    KeyCode::Down | KeyCode::Char('j') => {
-                        app.next();
-                    }
-                    KeyCode::Up | KeyCode::Char('k') => {
-                        app.prev();
-                    }
-                    KeyCode::Enter => {
-                        app.select();
-                        return Ok(());
-                    }
-                    _ => {}
-                }
+    /// Categorías de los grupos raíz, en orden de primera aparición
+    fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = Vec::new();
+        for category in self.config.groups.iter().filter_map(|g| g.category.as_deref()) {
+            if !categories.contains(&category) {
+                categories.push(category);
             }
         }
+        categories
     }
-}
 
-// ============================================================================
+    /// Categoría por la que se filtra la lista. Solo aplica al nivel raíz:
+    /// dentro de un grupo se ven todos sus subgrupos o apps
+    fn active_category(&self) -> Option<&str> {
+        if !self.nav.is_empty() || self.expanded.is_some() || self.tab == 0 {
+            return None;
+        }
+        self.categories().get(self.tab - 1).copied()
+    }
+
+    /// Pasar a la pestaña siguiente (`forward`) o anterior, volviendo al
+    /// nivel raíz
+    fn switch_tab(&mut self, forward: bool) {
+        let tabs = self.categories().len() + 1;
+        if tabs == 1 {
+            return;
+        }
+        self.tab = if forward {
+            (self.tab + 1) % tabs
+        } else {
+            (self.tab + tabs - 1) % tabs
+        };
+        self.nav.clear();
+        self.expanded = None;
+        self.load_level(0);
+    }
+
+    /// Búsqueda difusa (como fzf): se ocultan los grupos que no coinciden y el
+    /// resto se ordena de mejor a peor puntuación. Sin filtro se ven todos
+    /// los de la pestaña activa
+    fn refilter(&mut self) {
+        let (visible, positions) = fuzzy_filter(&self.matcher, &self.groups, &self.query);
+        (self.visible, self.match_positions) = match self.active_category() {
+            Some(category) => visible
+                .into_iter()
+                .zip(positions)
+                .filter(|(idx, _)| self.config.groups[*idx].category.as_deref() == Some(category))
+                .unzip(),
+            None => (visible, positions),
+        };
+        self.selected = self.selected.min(self.visible.len().saturating_sub(1));
+        self.sync_list_state();
+    }
+
+    /// Entrar en modo búsqueda
+    fn start_search(&mut self) {
+        self.searching = true;
+        self.recent_focus = None;
+    }
+
+    /// Salir del modo búsqueda limpiando el filtro.
+    /// La selección se conserva sobre el mismo grupo
+    fn clear_search(&mut self) {
+        let current = self.selected_group();
+        self.searching = false;
+        self.query.clear();
+        self.refilter();
+        if let Some(pos) = current.and_then(|idx| self.visible.iter().position(|&i| i == idx)) {
+            self.selected = pos;
+            self.sync_list_state();
+        }
+    }
+
+    /// Dejar de escribir en la búsqueda pero conservar el filtro, para
+    /// recorrer las coincidencias con `n`/`N`. Sin texto es como cancelarla
+    fn commit_search(&mut self) {
+        if self.query.is_empty() {
+            self.clear_search();
+        } else {
+            self.searching = false;
+        }
+    }
+
+    /// Saltar a la coincidencia siguiente (o anterior) de la búsqueda
+    /// confirmada, dando la vuelta en los extremos
+    fn next_match(&mut self, forward: bool) {
+        let count = self.visible.len();
+        if count == 0 {
+            return;
+        }
+        self.selected = if forward {
+            (self.selected + 1) % count
+        } else {
+            (self.selected + count - 1) % count
+        };
+        self.sync_list_state();
+    }
+
+    /// Al cambiar el filtro se resalta la mejor coincidencia
+    fn push_query(&mut self, c: char) {
+        self.query.push(c);
+        self.selected = 0;
+        self.refilter();
+    }
+
+    fn pop_query(&mut self) {
+        self.query.pop();
+        self.selected = 0;
+        self.refilter();
+    }
+
+    /// Lanzar el grupo seleccionado, o pedir confirmación si el grupo lo requiere.
+    /// Si el grupo tiene subgrupos, se navega hacia ellos
+    fn select(&mut self) {
+        if let Some(i) = self.recent_focus {
+            self.launch_recent(i);
+            return;
+        }
+
+        // Verificar que selected es válido
+        let Some(idx) = self.selected_group() else {
+            return;
+        };
+
+        // Con las apps de un grupo a la vista se lanza solo la resaltada
+        if let Some(group_idx) = self.expanded {
+            let key = self.group_key(group_idx);
+            let group = self.current_groups()[group_idx].clone();
+            self.select_app(key, group, idx);
+            return;
+        }
+
+        let group = &self.current_groups()[idx];
+        if !group.enabled {
+            self.set_status(StatusMessage::new(
+                format!("{} is disabled", group.name),
+                true,
+            ));
+            return;
+        }
+
+        // Los grupos con subgrupos se abren en lugar de lanzarse
+        if !group.subgroups.is_empty() {
+            self.descend(idx);
+            return;
+        }
+
+        let group = &self.current_groups()[idx];
+        let confirm = group.confirm;
+        if group.check_running {
+            let apps = group.launchable().apps;
+            self.check_running(PendingLaunch::Group(idx), self.group_key(idx), confirm, apps);
+        } else if confirm {
+            self.pending = Some(PendingLaunch::Group(idx));
+        } else {
+            self.launch(idx);
+        }
+    }
+
+    /// Lanzar una sola app de un grupo, o pedir confirmación si el grupo lo requiere
+    fn select_app(&mut self, key: String, group: Group, idx: usize) {
+        if group.check_running {
+            let (confirm, apps) = (group.confirm, vec![group.apps[idx].clone()]);
+            let pending = PendingLaunch::App { key: key.clone(), group: Box::new(group), idx };
+            self.check_running(pending, key, confirm, apps);
+        } else if group.confirm {
+            let group = Box::new(group);
+            self.pending = Some(PendingLaunch::App { key, group, idx });
+        } else {
+            self.launch_app(key, group, idx);
+        }
+    }
+
+    /// Buscar copias en marcha de `apps` en un hilo aparte (recorrer todos
+    /// los procesos lleva su tiempo) y decidir al llegar el recuento
+    /// (ver `poll_events`). Mientras tanto el footer muestra la búsqueda
+    fn check_running(
+        &mut self,
+        pending: PendingLaunch,
+        key: String,
+        confirm: bool,
+        apps: Vec<AppCommand>,
+    ) {
+        self.progress = Some(Progress {
+            key: key.clone(),
+            text: "Checking for running copies...".to_string(),
+            since: Instant::now(),
+        });
+        self.checking = Some((pending, key.clone(), confirm));
+        let tx = self.events_tx.clone();
+        std::thread::spawn(move || {
+            let count = running_instances(&apps);
+            let _ = tx.send(LaunchEvent::Running { key, count });
+        });
+    }
+
+    /// Seguir con el lanzamiento que esperaba el recuento de `check_running`:
+    /// se pregunta si hay copias en marcha o si el grupo tiene `confirm`
+    fn checked_running(&mut self, key: &str, count: usize) {
+        if self.checking.as_ref().is_none_or(|(_, k, _)| k != key) {
+            return;
+        }
+        let Some((pending, _, confirm)) = self.checking.take() else {
+            return;
+        };
+        if self.progress.as_ref().is_some_and(|p| p.key == key) {
+            self.progress = None;
+        }
+        self.pending_running = count;
+        if confirm || count > 0 {
+            self.pending = Some(pending);
+        } else {
+            self.launch_pending(pending);
+            // Como en `confirm_launch`: `L` ya quedó anotado al lanzar
+            if self.prompt_form.is_none() {
+                self.quit_after_launch = false;
+            }
+        }
+    }
+
+    /// Seleccionar y lanzar el grupo en la posición `n` (desde 1) de la lista
+    /// visible. Fuera de rango no hace nada
+    fn quick_select(&mut self, n: usize) {
+        if n == 0 || n > self.visible.len() {
+            return;
+        }
+        self.selected = n - 1;
+        self.sync_list_state();
+        self.select();
+    }
+
+    /// Confirmar el lanzamiento pendiente
+    fn confirm_launch(&mut self) {
+        self.pending_running = 0;
+        if let Some(pending) = self.pending.take() {
+            self.launch_pending(pending);
+        }
+        // Si se abrió el formulario de variables, la intención sigue pendiente
+        if self.prompt_form.is_none() {
+            self.quit_after_launch = false;
+        }
+    }
+
+    /// Teclas con el formulario de variables abierto: Enter responde la
+    /// variable actual y, tras la última, lanza con los valores escritos
+    fn prompt_key(&mut self, code: KeyCode) {
+        let Some(form) = &mut self.prompt_form else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => {
+                self.prompt_form = None;
+                self.quit_after_launch = false;
+            }
+            KeyCode::Enter => {
+                form.submit();
+                if form.current().is_none() {
+                    let Some(form) = self.prompt_form.take() else {
+                        return;
+                    };
+                    let group = form.group.with_values(&form.values);
+                    match form.app {
+                        Some(idx) => self.launch_app(form.key, group, idx),
+                        None => self.launch_prepared(form.key, group),
+                    }
+                    self.quit_after_launch = false;
+                }
+            }
+            KeyCode::Backspace => {
+                form.input.pop();
+            }
+            KeyCode::Char(c) => form.input.push(c),
+            _ => {}
+        }
+    }
+
+    /// Cancelar el lanzamiento pendiente
+    fn cancel_launch(&mut self) {
+        self.pending = None;
+        self.pending_running = 0;
+        self.quit_after_launch = false;
+        if let Some((_, key, _)) = self.checking.take() {
+            if self.progress.as_ref().is_some_and(|p| p.key == key) {
+                self.progress = None;
+            }
+        }
+    }
+
+    /// Lanzar lo que estaba pendiente (ya confirmado o sin nada que preguntar)
+    fn launch_pending(&mut self, pending: PendingLaunch) {
+        match pending {
+            PendingLaunch::Group(idx) => self.launch(idx),
+            PendingLaunch::App { key, group, idx } => self.launch_app(key, *group, idx),
+            PendingLaunch::All => self.launch_all(),
+        }
+    }
+
+    /// Si hay un lanzamiento esperando confirmación
+    fn confirming(&self) -> bool {
+        self.pending.is_some()
+    }
+
+    /// Lanzar todos los grupos de la configuración (incluidos los subgrupos).
+    /// Si alguno pide confirmación, se pregunta una vez para todos
+    fn select_all(&mut self) {
+        let groups = flatten_groups(&self.config.groups);
+        if groups.iter().any(|(_, g)| g.confirm) {
+            self.pending = Some(PendingLaunch::All);
+        } else {
+            self.launch_all();
+        }
+    }
+
+    /// Lanzar todos los grupos uno tras otro en un hilo, registrando los
+    /// procesos de cada uno y dejando un resumen total en el footer
+    fn launch_all(&mut self) {
+        if self.cooling_down() {
+            return;
+        }
+        // No hay a quién preguntar por cada grupo: las variables toman su valor
+        // por defecto y los grupos con alguna obligatoria se omiten
+        let (groups, skipped): (Vec<_>, Vec<_>) = flatten_groups(&self.config.groups)
+            .into_iter()
+            .partition(|(_, g)| g.missing_prompt(&g.prompt_defaults()).is_none());
+        let mut groups: Vec<(String, Group)> = groups
+            .into_iter()
+            .map(|(key, g)| (key, g.with_values(&g.prompt_defaults())))
+            .collect();
+        let skipped = match skipped.len() {
+            0 => String::new(),
+            n => format!(", {} skipped: need input", n),
+        };
+
+        if self.dry_run {
+            let opts = self.launch_options();
+            let mut commands = 0;
+            for (key, group) in &groups {
+                self.dry_run_log.push(format!("# {}", key));
+                self.dry_run_log.extend(group_commands(group, &opts));
+                commands += group.apps.len();
+            }
+            self.set_status(StatusMessage::new(
+                format!("Dry run: all groups ({} commands{})", commands, skipped),
+                false,
+            ));
+            return;
+        }
+
+        self.set_status(StatusMessage::new(
+            format!("Launching {} groups...", groups.len()),
+            false,
+        ));
+        for (key, group) in &mut groups {
+            let interactive = self.take_interactive(key, group);
+            self.queue_foreground(key, interactive);
+        }
+
+        let tx = self.events_tx.clone();
+        let mut opts = self.launch_options();
+        // `launch_workers` es el total: los hilos que quedan después de
+        // repartir los grupos lanzan las apps de cada uno
+        let workers = opts.launch_workers.min(groups.len()).max(1);
+        opts.launch_workers = (opts.launch_workers / workers).max(1);
+        let handle = std::thread::spawn(move || {
+            // Cada hilo toma el siguiente grupo pendiente, así un grupo lento
+            // (`wait_for`, `delay_ms`) no frena a los demás si hay más de uno
+            let queue = Mutex::new(groups.into_iter());
+            let worker = || {
+                let (mut ok, mut total) = (0, 0);
+                while let Some((key, group)) = queue.lock().ok().and_then(|mut q| q.next()) {
+                    let apps = group.apps.len();
+                    let results = launch_group_progress(&group, &opts, |i, app| {
+                        report_progress(&tx, &key, apps, i, app)
+                    });
+                    total += results.len();
+                    ok += results.iter().filter(|r| r.is_ok()).count();
+                    let _ = tx.send(finished_event(key, &group, results));
+                }
+                (ok, total)
+            };
+            let (ok, total) = std::thread::scope(|s| {
+                let handles: Vec<_> = (0..workers).map(|_| s.spawn(worker)).collect();
+                handles
+                    .into_iter()
+                    .filter_map(|h| h.join().ok())
+                    .fold((0, 0), |(ok, total), (o, t)| (ok + o, total + t))
+            });
+            let _ = tx.send(LaunchEvent::AllFinished {
+                summary: format!("Launched all groups ({}/{} apps{})", ok, total, skipped),
+                error: ok < total,
+            });
+        });
+        self.track_launch(handle);
+    }
+
+    /// Ejecutar todas las aplicaciones de un grupo del nivel actual
+    /// y guardar un resumen del resultado en `last_result`.
+    /// El lanzamiento corre en un hilo aparte para que las pausas
+    /// (`delay_ms`, grupos secuenciales) no congelen la interfaz
+    fn launch(&mut self, idx: usize) {
+        // Acceder al grupo desde la configuración
+        let group = self.current_groups()[idx].launchable();
+        let key = self.group_key(idx);
+
+        // Con variables, primero se piden y el lanzamiento sigue en `fill_prompt`
+        if !group.prompts.is_empty() {
+            self.prompt_form = Some(PromptForm::new(key, group, None));
+            return;
+        }
+        self.launch_prepared(key, group);
+    }
+
+    /// Lanzar un grupo ya listo (habilitado, ordenado y con sus variables
+    /// reemplazadas)
+    fn launch_prepared(&mut self, key: String, group: Group) {
+        if self.cooling_down() {
+            return;
+        }
+        // En dry-run solo se registra lo que se ejecutaría
+        if self.dry_run {
+            let opts = self.launch_options();
+            self.dry_run_log.push(format!("# {}", key));
+            self.dry_run_log.extend(group_commands(&group, &opts));
+            self.set_status(StatusMessage::new(
+                format!("Dry run: {} ({} commands)", key, group.apps.len()),
+                false,
+            ));
+            return;
+        }
+
+        self.set_status(StatusMessage::new(
+            format!("Launching {}...", group.name),
+            false,
+        ));
+        self.start_group(key, group);
+    }
+
+    /// Lanzar un grupo en un hilo aparte. Sus apps `interactive` esperan a
+    /// que el resto del grupo y sus hooks terminen (ver `take_interactive`)
+    fn start_group(&mut self, key: String, mut group: Group) {
+        let interactive = self.take_interactive(&key, &mut group);
+        if !interactive.is_empty() {
+            self.queue_foreground(&key, interactive);
+            return;
+        }
+
+        let tx = self.events_tx.clone();
+        let opts = self.launch_options();
+        let handle = std::thread::spawn(move || {
+            let total = group.apps.len();
+            let results = launch_group_progress(&group, &opts, |i, app| {
+                report_progress(&tx, &key, total, i, app)
+            });
+            // Si el launcher ya cerró nadie escucha; no es un error
+            let _ = tx.send(finished_event(key, &group, results));
+        });
+        self.track_launch(handle);
+    }
+
+    /// Sacar del grupo sus apps `interactive`: usan la terminal, así que no
+    /// pueden lanzarse desde el hilo de lanzamiento. El resto del grupo (y sus
+    /// hooks) se lanza como siempre, y al terminar (ver `poll_events`) las
+    /// interactivas corren una tras otra en primer plano. Se devuelven para
+    /// los grupos que no dejaron nada que lanzar antes
+    fn take_interactive(&mut self, key: &str, group: &mut Group) -> Vec<Group> {
+        let (interactive, apps): (Vec<_>, Vec<_>) =
+            std::mem::take(&mut group.apps).into_iter().partition(|a| a.interactive);
+        group.apps = apps;
+        let interactive: Vec<_> = interactive.iter().map(|a| group.with_only(a)).collect();
+        if group.apps.is_empty() && group.pre.is_none() && group.post.is_none() {
+            return interactive;
+        }
+        if !interactive.is_empty() {
+            self.after_launch.entry(key.to_string()).or_default().extend(interactive);
+        }
+        Vec::new()
+    }
+
+    /// Poner apps `interactive` en la cola de primer plano. Con `L` se sale
+    /// cuando terminen
+    fn queue_foreground(&mut self, key: &str, apps: Vec<Group>) {
+        if apps.is_empty() {
+            return;
+        }
+        self.foreground.extend(apps.into_iter().map(|a| (key.to_string(), a)));
+        if self.quit_after_launch {
+            self.quit_when_done = true;
+        }
+    }
+
+    /// Abrir la paleta de comandos con todas las apps habilitadas
+    fn open_palette(&mut self) {
+        self.palette = Some(Palette::new(&self.config));
+    }
+
+    /// Teclas con la paleta abierta: escribir filtra, Enter lanza la app
+    fn palette_key(&mut self, code: KeyCode) {
+        let Some(palette) = &mut self.palette else {
+            return;
+        };
+        match code {
+            KeyCode::Esc => self.palette = None,
+            KeyCode::Enter => {
+                if let Some((key, group, idx)) = palette.selected() {
+                    self.palette = None;
+                    self.select_app(key, group, idx);
+                }
+            }
+            KeyCode::Down => palette.next(),
+            KeyCode::Up => palette.prev(),
+            KeyCode::Backspace => {
+                palette.query.pop();
+                palette.refilter(&self.matcher);
+            }
+            KeyCode::Char(c) => {
+                palette.query.push(c);
+                palette.refilter(&self.matcher);
+            }
+            _ => {}
+        }
+    }
+
+    /// Lanzar una sola app (índice `idx`) de un grupo. Sus procesos se
+    /// registran bajo la clave del grupo
+    fn launch_app(&mut self, key: String, mut group: Group, idx: usize) {
+        let cmd = group.apps[idx].clone();
+
+        if !cmd.enabled {
+            self.set_status(StatusMessage::new(
+                format!("{} is disabled", cmd.name),
+                true,
+            ));
+            return;
+        }
+
+        if !group.prompts.is_empty() {
+            self.prompt_form = Some(PromptForm::new(key, group, Some(idx)));
+            return;
+        }
+
+        if self.cooling_down() {
+            return;
+        }
+
+        // Se lanza como un grupo de una sola app para conservar el registro,
+        // `wait`, `timeout_ms` y los hooks
+        group.apps = vec![cmd.clone()];
+
+        if self.dry_run {
+            self.dry_run_log.push(format!("# {} › {}", key, cmd.name));
+            let opts = self.launch_options();
+            self.dry_run_log.extend(group_commands(&group, &opts));
+            self.set_status(StatusMessage::new(
+                format!("Dry run: {} › {} (1 command)", key, cmd.name),
+                false,
+            ));
+            return;
+        }
+
+        self.set_status(StatusMessage::new(
+            format!("Launching {}...", cmd.name),
+            false,
+        ));
+        // Una app interactiva espera a los hooks del grupo, si los tiene
+        if cmd.interactive {
+            self.start_group(key, group);
+            return;
+        }
+
+        let tx = self.events_tx.clone();
+        let opts = self.launch_options();
+        let handle = std::thread::spawn(move || {
+            let res = launch_group_with(&group, &opts)
+                .pop()
+                .unwrap_or_else(|| Err(io::Error::other("not launched")));
+            let app = &group.apps[0];
+            let (summary, error) = match &res {
+                Ok(_) => (format!("Launched {} from {}", app.name, group.name), false),
+                Err(e) => (format!("Failed to launch {}: {}", app.name, e), true),
+            };
+            let _ = tx.send(LaunchEvent::Finished {
+                key,
+                summary,
+                error,
+                apps: vec![TrackedApp::new(&group, app, res)],
+            });
+        });
+        self.track_launch(handle);
+    }
+
+    /// Si el último lanzamiento fue hace menos de `LAUNCH_COOLDOWN`; en ese
+    /// caso el nuevo se ignora y se avisa en el footer. Si no, se anota este
+    fn cooling_down(&mut self) -> bool {
+        let now = Instant::now();
+        if self
+            .last_launch
+            .is_some_and(|at| now.duration_since(at) < LAUNCH_COOLDOWN)
+        {
+            self.set_status(StatusMessage::new("Already launching...".to_string(), false));
+            return true;
+        }
+        self.last_launch = Some(now);
+        false
+    }
+
+    /// Registrar un hilo de lanzamiento. Si se lanzó con `L`, el launcher se
+    /// cierra en cuanto terminen los lanzamientos en curso
+    fn track_launch(&mut self, handle: JoinHandle<()>) {
+        self.launches.push(handle);
+        if self.quit_after_launch {
+            self.quit_when_done = true;
+        }
+    }
+
+    /// Si todavía hay hilos de lanzamiento en curso
+    fn launching(&mut self) -> bool {
+        self.launches.retain(|h| !h.is_finished());
+        !self.launches.is_empty()
+    }
+
+    /// Lanzar lo seleccionado igual que con Enter y después salir.
+    /// Si el grupo pide confirmación, la intención se guarda hasta responder
+    fn select_and_quit(&mut self) {
+        self.quit_after_launch = true;
+        self.select();
+        if !self.confirming() && self.prompt_form.is_none() && self.checking.is_none() {
+            self.quit_after_launch = false;
+        }
+    }
+
+    /// Clave única de un grupo del nivel actual: su ruta completa de nombres,
+    /// ya que los índices se repiten entre niveles
+    fn group_key(&self, idx: usize) -> String {
+        let mut path = self.breadcrumbs();
+        path.push(&self.current_groups()[idx].name);
+        path.join(" › ")
+    }
+
+    /// Enviar SIGTERM a los procesos lanzados por el grupo seleccionado
+    /// Con las apps de un grupo a la vista, solo se detiene la resaltada
+    fn stop_selected(&mut self) {
+        let Some(idx) = self.selected_group() else {
+            return;
+        };
+        let (key, only) = match self.expanded {
+            Some(group_idx) => (self.group_key(group_idx), Some(self.groups[idx].clone())),
+            None => (self.group_key(idx), None),
+        };
+        // Lo detenido a mano no se reinicia, ni lo que esperaba reiniciarse
+        let mut pids = Vec::new();
+        let mut cancelled = 0;
+        let matching = self.processes.get_mut(&key).into_iter().flatten();
+        for p in matching.filter(|p| only.as_ref().is_none_or(|name| &p.name == name)) {
+            p.stopped = true;
+            cancelled += usize::from(p.restart_at.take().is_some());
+            if p.status == AppStatus::Running {
+                pids.extend(p.child.as_ref().map(|c| c.id()));
+            }
+        }
+
+        if pids.is_empty() && cancelled > 0 {
+            self.set_status(StatusMessage::new(
+                format!("Stopped {}: cancelled {} restart(s)", key, cancelled),
+                false,
+            ));
+            return;
+        }
+        if pids.is_empty() {
+            self.set_status(StatusMessage::new(
+                format!("No tracked processes for {}", key),
+                false,
+            ));
+            return;
+        }
+
+        let signaled = pids.iter().filter(|&&pid| terminate(pid).is_ok()).count();
+        self.set_status(StatusMessage::new(
+            format!("Stopped {}: signaled {}/{} processes", key, signaled, pids.len()),
+            signaled < pids.len(),
+        ));
+    }
+
+    /// Procesar los eventos pendientes de los hilos de lanzamiento.
+    /// Devuelve true si llegó alguno
+    fn poll_events(&mut self) -> bool {
+        let mut changed = false;
+        while let Ok(event) = self.events_rx.try_recv() {
+            changed = true;
+            match event {
+                LaunchEvent::Finished {
+                    key,
+                    summary,
+                    error,
+                    mut apps,
+                } => {
+                    for app in &mut apps {
+                        app.capture(self.attach_log.as_ref());
+                    }
+                    if apps.iter().any(|a| !matches!(a.status, AppStatus::Failed(_))) {
+                        self.launched.insert(key.clone(), chrono::Utc::now().timestamp());
+                        self.remember_recent(&key);
+                    }
+                    if self.progress.as_ref().is_some_and(|p| p.key == key) {
+                        self.progress = None;
+                    }
+                    if let Some(interactive) = self.after_launch.remove(&key) {
+                        self.queue_foreground(&key, interactive);
+                    }
+                    self.processes.entry(key).or_default().extend(apps);
+                    self.set_status(StatusMessage::new(summary, error));
+                }
+                LaunchEvent::AllFinished { summary, error } => {
+                    self.progress = None;
+                    self.set_status(StatusMessage::new(summary, error));
+                }
+                LaunchEvent::Progress { key, text } => {
+                    self.progress = Some(Progress {
+                        key,
+                        text,
+                        since: Instant::now(),
+                    });
+                }
+                LaunchEvent::Running { key, count } => self.checked_running(&key, count),
+                LaunchEvent::Restarted { id, res } => {
+                    let log = self.attach_log.as_ref();
+                    let mut apps = self.processes.values_mut().flatten();
+                    match apps.find(|a| a.id == id) {
+                        Some(app) => {
+                            let note = app.restarted(res, log);
+                            self.log(note);
+                        }
+                        // Ya no se sigue (no debería pasar): no dejarla suelta
+                        None => {
+                            if let Ok(child) = res {
+                                let _ = terminate(child.id());
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        changed
+    }
+
+    /// Consultar el estado de los procesos lanzados.
+    /// Devuelve true si alguno cambió de estado
+    fn poll_processes(&mut self) -> bool {
+        let mut changed = false;
+        let mut failed_output = None;
+        let mut results = Vec::new();
+        let mut notes = Vec::new();
+        let opts = self.launch_options();
+        for app in self.processes.values_mut().flatten() {
+            app.restart_if_due(&self.events_tx, &opts);
+            if app.poll() {
+                changed = true;
+                if let Some(message) = app.oneshot_result() {
+                    if message.error {
+                        failed_output = app.output_popup().or(failed_output);
+                    }
+                    results.push(message);
+                } else {
+                    notes.push(match &app.status {
+                        AppStatus::Exited(Some(code)) => format!("{} exited ({})", app.name, code),
+                        AppStatus::Exited(None) => format!("{} was killed", app.name),
+                        AppStatus::Failed(e) => format!("{} failed: {}", app.name, e),
+                        AppStatus::Running => continue,
+                    });
+                }
+            }
+        }
+        for note in notes {
+            self.log(note);
+        }
+        for message in results {
+            self.set_status(message);
+        }
+        // Mostrar por qué falló, salvo que otra ventana espere al usuario
+        let busy = self.show_help
+            || self.confirm_quit
+            || self.confirming()
+            || self.palette.is_some()
+            || self.prompt_form.is_some()
+            || self.output_popup.is_some();
+        if !busy {
+            self.output_popup = failed_output;
+        }
+        changed
+    }
+
+    /// Redibujar si llegó salida nueva al log de `--attach` o al popup abierto
+    fn poll_output(&mut self) -> bool {
+        let popup = self.output_popup.as_ref().map(|p| &p.output);
+        let received: usize = popup.into_iter().chain(&self.attach_log).map(|o| o.received()).sum();
+        let changed = received != self.output_seen;
+        self.output_seen = received;
+        changed
+    }
+
+    /// Abrir (`o`) la salida capturada de la última tarea `oneshot` lanzada
+    /// del grupo (o de la app) seleccionado
+    fn show_output(&mut self) {
+        let Some(idx) = self.selected_group() else {
+            return;
+        };
+        let (key, only) = match self.expanded {
+            Some(group_idx) => (self.group_key(group_idx), Some(self.groups[idx].clone())),
+            None => (self.group_key(idx), None),
+        };
+        let popup = self
+            .processes
+            .get(&key)
+            .into_iter()
+            .flatten()
+            .rev()
+            .filter(|p| only.as_ref().is_none_or(|name| &p.name == name))
+            .find_map(TrackedApp::output_popup);
+        if popup.is_none() {
+            self.set_status(StatusMessage::new(
+                format!("No captured output for {}", key),
+                false,
+            ));
+        }
+        self.output_popup = popup;
+    }
+
+    /// Teclas con el popup de salida abierto: desplazarse o cerrarlo
+    fn output_key(&mut self, code: KeyCode) {
+        let Some(popup) = &mut self.output_popup else {
+            return;
+        };
+        let last = popup.output.lines().len().saturating_sub(1) as u16;
+        popup.scroll = match code {
+            KeyCode::Down | KeyCode::Char('j') => popup.scroll.saturating_add(1),
+            KeyCode::Up | KeyCode::Char('k') => popup.scroll.saturating_sub(1),
+            KeyCode::PageDown => popup.scroll.saturating_add(10),
+            KeyCode::PageUp => popup.scroll.saturating_sub(10),
+            KeyCode::Home | KeyCode::Char('g') => 0,
+            KeyCode::End | KeyCode::Char('G') => last,
+            KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') | KeyCode::Char('o') => {
+                self.output_popup = None;
+                return;
+            }
+            _ => return,
+        }
+        .min(last);
+    }
+
+    /// Opciones de lanzamiento de la TUI: la salida de las tareas `oneshot`
+    /// se captura para poder mostrarla (ver `OutputPopup`)
+    fn launch_options(&self) -> LaunchOptions {
+        LaunchOptions {
+            capture_output: true,
+            attach: self.attach_log.is_some(),
+            ..LaunchOptions::from_config(&self.config)
+        }
+    }
+
+    /// Terminar las apps que siguen en marcha (al salir con `--attach`)
+    fn stop_all(&self) {
+        let running = self.processes.values().flatten().filter(|p| p.status == AppStatus::Running);
+        for pid in running.filter_map(|p| p.child.as_ref().map(|c| c.id())) {
+            let _ = terminate(pid);
+        }
+    }
+
+    /// Cantidad de apps lanzadas en esta sesión que siguen en marcha
+    fn running_count(&self) -> usize {
+        self.processes
+            .values()
+            .flatten()
+            .filter(|p| matches!(p.status, AppStatus::Running))
+            .count()
+    }
+
+    /// Lanzamiento más reciente de una app del grupo `key`, si se lanzó
+    fn tracked_app(&self, key: &str, app: &str) -> Option<&TrackedApp> {
+        self.processes.get(key)?.iter().rev().find(|p| p.name == app)
+    }
+
+    /// Recargar la configuración si el observador vio cambios en el archivo
+    fn poll_watcher(&mut self) -> bool {
+        if !self.watcher.as_mut().is_some_and(|w| w.changed()) {
+            return false;
+        }
+        self.reload_as("Config auto-reloaded");
+        true
+    }
+
+    /// Recarga pedida con `r`
+    fn reload(&mut self) {
+        self.reload_as("Config reloaded");
+    }
+
+    /// Volver a leer la configuración del mismo origen con el que se arrancó.
+    /// Si falla, se mantiene la configuración actual y se muestra el error.
+    /// `label` encabeza el mensaje del footer
+    fn reload_as(&mut self, label: &str) {
+        match Config::load_validated(self.explicit_config.as_ref(), self.prefer_global) {
+            Ok((config, path)) => {
+                // Mantener la selección sobre el mismo grupo si sigue existiendo.
+                // La navegación vuelve al nivel raíz porque los índices pueden cambiar
+                let current = match self.nav.first() {
+                    Some(&root) => Some(self.config.groups[root].name.clone()),
+                    None => self.selected_group().map(|i| self.groups[i].clone()),
+                };
+                let warnings = config.warnings.len();
+                if config.mouse != self.config.mouse {
+                    let _ = set_mouse_capture(config.mouse);
+                }
+                for warning in &config.warnings {
+                    self.log(format!("Warning: {}", warning));
+                }
+                self.config = config;
+                self.config_path = path;
+                self.nav.clear();
+                self.expanded = None;
+                if self.tab > self.categories().len() {
+                    self.tab = 0;
+                }
+                self.groups = self.config.groups.iter().map(|g| g.name.clone()).collect();
+                self.cancel_launch();
+                self.refilter();
+                if let Some(pos) = current
+                    .and_then(|name| self.visible.iter().position(|&i| self.groups[i] == name))
+                {
+                    self.selected = pos;
+                    self.sync_list_state();
+                }
+                let mut msg = format!("{} ({} groups)", label, self.groups.len());
+                if warnings > 0 {
+                    msg.push_str(&format!(", {} warning(s)", warnings));
+                }
+                self.set_status(StatusMessage::new(msg, false));
+            }
+            Err(e) => {
+                self.set_status(StatusMessage::new(format!("Reload failed: {}", e), true));
+            }
+        }
+    }
+
+    /// Mostrar un mensaje en el footer y guardarlo en el registro
+    fn set_status(&mut self, message: StatusMessage) {
+        self.log(message.text.clone());
+        self.last_result = Some(message);
+    }
+
+    /// Agregar una entrada al registro de la sesión
+    fn log(&mut self, text: String) {
+        if self.messages.len() >= MESSAGE_LOG_LINES {
+            self.messages.pop_front();
+        }
+        self.messages.push_back((Instant::now(), text));
+        // Desplazado hacia atrás, el panel sigue mostrando las mismas entradas
+        if self.messages_scroll > 0 {
+            self.messages_scroll += 1;
+        }
+    }
+
+    /// Abrir o cerrar el panel del registro (`m`), mostrando lo último
+    fn toggle_messages(&mut self) {
+        self.show_messages = !self.show_messages;
+        self.messages_scroll = 0;
+    }
+
+    /// Desplazar el panel del registro: `[` hacia las entradas más antiguas,
+    /// `]` hacia las más recientes. El tope lo ajusta el dibujado
+    fn scroll_messages(&mut self, older: bool) {
+        self.messages_scroll = if older {
+            self.messages_scroll + 1
+        } else {
+            self.messages_scroll.saturating_sub(1)
+        };
+    }
+
+    /// Descartar el mensaje de estado si ya expiró
+    fn expire_status(&mut self) -> bool {
+        if self.last_result.as_ref().is_some_and(|m| m.expired()) {
+            self.last_result = None;
+            return true;
+        }
+        false
+    }
+
+    /// Resaltar el grupo de la sesión anterior, si todavía existe
+    fn restore_state(&mut self, state: &SavedState) {
+        self.launched = state.launched.clone();
+        self.recent = state.recent.clone();
+        let Some(last) = &state.last else {
+            return;
+        };
+        if let Some(pos) = self.visible.iter().position(|&i| &self.groups[i] == last) {
+            self.selected = pos;
+            self.sync_list_state();
+        }
+    }
+
+    /// Estado a guardar al salir: el grupo raíz resaltado (o abierto)
+    fn saved_state(&self) -> SavedState {
+        let root = self.nav.first().copied().or(self.expanded);
+        let last = match root {
+            Some(root) => Some(self.config.groups[root].name.clone()),
+            None => self.selected_group().map(|i| self.groups[i].clone()),
+        };
+        SavedState {
+            last,
+            launched: self.launched.clone(),
+            recent: self.recent.clone(),
+        }
+    }
+
+    /// Clic para seleccionar, doble clic para lanzar y rueda para moverse
+    fn handle_mouse(&mut self, mouse: MouseEvent) {
+        if self.show_help {
+            if let MouseEventKind::Down(_) = mouse.kind {
+                self.show_help = false;
+            }
+            return;
+        }
+        if self.confirming() || self.confirm_quit || self.checking.is_some() {
+            return;
+        }
+        if self.palette.is_some() || self.prompt_form.is_some() || self.output_popup.is_some() {
+            return;
+        }
+
+        match mouse.kind {
+            MouseEventKind::ScrollDown => self.next(),
+            MouseEventKind::ScrollUp => self.prev(),
+            MouseEventKind::Down(MouseButton::Left) => {
+                let Some(pos) = self.row_at(mouse.column, mouse.row) else {
+                    return;
+                };
+                self.selected = pos;
+                self.sync_list_state();
+
+                let double = self
+                    .last_click
+                    .is_some_and(|(row, at)| row == pos && at.elapsed() < DOUBLE_CLICK);
+                if double {
+                    self.last_click = None;
+                    self.select();
+                } else {
+                    self.last_click = Some((pos, Instant::now()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Posición en `visible` de la fila bajo el cursor, si cae dentro de la lista
+    fn row_at(&self, column: u16, row: u16) -> Option<usize> {
+        let area = self.list_area;
+        // Descontar los bordes del bloque
+        let inside = column > area.x
+            && column < area.right().saturating_sub(1)
+            && row > area.y
+            && row < area.bottom().saturating_sub(1);
+        if !inside {
+            return None;
+        }
+        if self.list_columns > 1 {
+            // Las columnas se reparten el ancho interior a partes iguales
+            let width = area.width.saturating_sub(2).max(1) as usize;
+            let column = (column - area.x - 1) as usize * self.list_columns / width;
+            let line = (row - area.y - 1) as usize + self.column_offset;
+            let pos = column * self.column_rows + line;
+            return (line < self.column_rows && pos < self.visible.len()).then_some(pos);
+        }
+        let pos = (row - area.y - 1) as usize + self.list_state.offset();
+        (pos < self.visible.len()).then_some(pos)
+    }
+}
+
+// ============================================================================
+// CLI - Argumentos de línea de comandos
+// ============================================================================
+
+/// Subcomando a ejecutar
+#[derive(Debug, Default, PartialEq)]
+enum CliCommand {
+    /// Interfaz interactiva (por defecto)
+    #[default]
+    Tui,
+    /// `launcher list [--verbose] [--output text|json]`: imprimir grupos sin TUI
+    List { verbose: bool, json: bool },
+    /// `launcher run <group> [--ignore-case]`: lanzar un grupo sin TUI
+    Run { group: String, ignore_case: bool },
+    /// `launcher run-all`: lanzar todos los grupos sin TUI
+    RunAll,
+    /// `launcher schema`: imprimir el JSON Schema de la configuración
+    Schema,
+    /// `launcher --version`: imprimir la versión y salir
+    Version,
+    /// `launcher dump [--format json|yaml|toml]`: imprimir la configuración
+    /// cargada, con los valores por defecto aplicados
+    Dump { format: String },
+    /// `launcher serve`: escuchar órdenes en un socket Unix
+    Serve,
+    /// `launcher doctor`: mostrar dónde se busca la configuración
+    Doctor,
+}
+
+/// Formatos de salida de `launcher dump`
+const DUMP_FORMATS: [&str; 3] = ["json", "yaml", "toml"];
+
+/// Opciones recibidas por línea de comandos
+#[derive(Debug, Default)]
+struct Cli {
+    /// Ruta explícita al archivo de configuración (`--config` / `-c`)
+    config: Option<PathBuf>,
+    command: CliCommand,
+    /// Mostrar los comandos en lugar de ejecutarlos (`--dry-run`)
+    dry_run: bool,
+    /// Recargar la configuración al cambiar el archivo (`--watch`)
+    watch: bool,
+    /// Dibujar la TUI sin colores (`--no-color`, o la variable `NO_COLOR`)
+    no_color: bool,
+    /// Buscar en `~/.config/launcher` antes que en el directorio actual
+    /// (`--prefer-global`)
+    prefer_global: bool,
+    /// No detenerse a mostrar los avisos de la configuración (`--force`)
+    force: bool,
+    /// No desacoplar las apps y mostrar su salida en la TUI (`--attach`)
+    attach: bool,
+}
+
+impl Cli {
+    /// Parsear los argumentos (sin incluir el nombre del binario)
+    fn parse<I: IntoIterator<Item = String>>(args: I) -> Result<Self, String> {
+        let mut cli = Cli::default();
+        let mut args = args.into_iter();
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-n" | "--dry-run" => cli.dry_run = true,
+                "-w" | "--watch" => cli.watch = true,
+                "--no-color" => cli.no_color = true,
+                "--prefer-global" => cli.prefer_global = true,
+                "--force" => cli.force = true,
+                "--attach" => cli.attach = true,
+                "-V" | "--version" => cli.command = CliCommand::Version,
+                "-c" | "--config" => {
+                    let path = args
+                        .next()
+                        .ok_or_else(|| format!("{} requires a path argument", arg))?;
+                    cli.config = Some(expand_path(&path));
+                }
+                _ if arg.starts_with("--config=") => {
+                    cli.config = Some(expand_path(&arg["--config=".len()..]));
+                }
+                "list" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::List {
+                        verbose: false,
+                        json: false,
+                    };
+                }
+                "-v" | "--verbose" => match &mut cli.command {
+                    CliCommand::List { verbose, .. } => *verbose = true,
+                    _ => return Err(format!("{} is only valid with `list`", arg)),
+                },
+                "run" if cli.command == CliCommand::Tui => {
+                    let group = args
+                        .next()
+                        .ok_or_else(|| "run requires a group name".to_string())?;
+                    cli.command = CliCommand::Run {
+                        group,
+                        ignore_case: false,
+                    };
+                }
+                "run-all" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::RunAll;
+                }
+                "schema" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::Schema;
+                }
+                "serve" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::Serve;
+                }
+                "doctor" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::Doctor;
+                }
+                "dump" if cli.command == CliCommand::Tui => {
+                    cli.command = CliCommand::Dump {
+                        format: "json".to_string(),
+                    };
+                }
+                "-f" | "--format" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("{} requires a format argument", arg))?;
+                    match &mut cli.command {
+                        CliCommand::Dump { format } if DUMP_FORMATS.contains(&value.as_str()) => {
+                            *format = value
+                        }
+                        CliCommand::Dump { .. } => {
+                            return Err(format!(
+                                "unknown format {:?} (expected {})",
+                                value,
+                                DUMP_FORMATS.join(", ")
+                            ))
+                        }
+                        _ => return Err(format!("{} is only valid with `dump`", arg)),
+                    }
+                }
+                "-o" | "--output" => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("{} requires a format argument", arg))?;
+                    match (&mut cli.command, value.as_str()) {
+                        (CliCommand::List { json, .. }, "json" | "text") => *json = value == "json",
+                        (CliCommand::List { .. }, _) => {
+                            return Err(format!("unknown output {:?} (expected text, json)", value))
+                        }
+                        _ => return Err(format!("{} is only valid with `list`", arg)),
+                    }
+                }
+                "-i" | "--ignore-case" => match &mut cli.command {
+                    CliCommand::Run { ignore_case, .. } => *ignore_case = true,
+                    _ => return Err(format!("{} is only valid with `run`", arg)),
+                },
+                _ => return Err(format!("Unknown argument: {}", arg)),
+            }
+        }
+
+        Ok(cli)
+    }
+}
+
+/// Versión del crate y, si se compiló desde git, el commit: `0.1.0 (1a2b3c4)`
+fn version() -> String {
+    let version = env!("CARGO_PKG_VERSION");
+    match option_env!("LAUNCHER_GIT_HASH") {
+        Some(hash) => format!("{} ({})", version, hash),
+        None => version.to_string(),
+    }
+}
+
+/// Buscar un grupo por nombre o alias (opcionalmente sin distinguir mayúsculas),
+/// incluyendo los subgrupos
+fn find_group<'a>(groups: &'a [Group], name: &str, ignore_case: bool) -> Option<&'a Group> {
+    groups.iter().find_map(|g| {
+        if g.answers_to(name, ignore_case) {
+            Some(g)
+        } else {
+            find_group(&g.subgroups, name, ignore_case)
+        }
+    })
+}
+
+/// Procesos en marcha con el nombre de alguna de `apps` si el grupo tiene
+/// `check_running`; 0 si no lo tiene
+fn already_running(group: &Group, apps: &[AppCommand]) -> usize {
+    if group.check_running {
+        running_instances(apps)
+    } else {
+        0
+    }
+}
+
+/// Lanzar todos los grupos desde la línea de comandos. Los grupos con
+/// `confirm` se preguntan por stdin y se omiten si no se responde "y"
+fn run_all(config: &Config, dry_run: bool) {
+    let opts = LaunchOptions::from_config(config);
+    let (mut ok, mut total, mut launched) = (0, 0, 0);
+
+    for (key, group) in flatten_groups(&config.groups) {
+        if dry_run {
+            println!("# {}", key);
+            let group = group.with_values(&group.prompt_defaults());
+            for line in group_commands(&group, &opts) {
+                println!("{}", line);
+            }
+            continue;
+        }
+        if group.confirm && !ask(&format!("Launch '{}'? [y/N] ", key)) {
+            println!("Skipped {}", key);
+            continue;
+        }
+        let running = already_running(&group, &group.launchable().apps);
+        if running > 0 {
+            let question = format!(
+                "{} process(es) of '{}' already running; launch anyway? [y/N] ",
+                running, key
+            );
+            if !ask(&question) {
+                println!("Skipped {}", key);
+                continue;
+            }
+        }
+        let Some(group) = ask_prompts(&group) else {
+            println!("Skipped {}: missing prompt value", key);
+            continue;
+        };
+
+        let results = launch_group_with(&group, &opts);
+        for (app, res) in group.apps.iter().zip(&results) {
+            if let Err(e) = res {
+                eprintln!("Error al ejecutar {}: {}", app.name, e);
+            }
+        }
+        total += results.len();
+        ok += results.iter().filter(|r| r.is_ok()).count();
+        launched += 1;
+    }
+
+    if !dry_run {
+        println!("Launched {}/{} apps from {} group(s)", ok, total, launched);
+    }
+}
+
+/// Imprimir una tabla app → código de salida de las apps con `wait`
+fn print_exit_codes(group: &Group, results: &mut [io::Result<Child>]) {
+    let width = group.apps.iter().map(|a| a.name.len()).max().unwrap_or(0).max(3);
+    println!();
+    println!("{:<width$}  EXIT", "APP", width = width);
+    for (app, res) in group.apps.iter().zip(results.iter_mut()) {
+        let code = match res {
+            Err(e) => format!("failed: {}", e),
+            Ok(_) if !app.wait && !app.oneshot => "running".to_string(),
+            Ok(child) => match child.try_wait() {
+                Ok(Some(status)) => match status.code() {
+                    Some(code) => code.to_string(),
+                    None => "killed".to_string(),
+                },
+                _ => "running".to_string(),
+            },
+        };
+        println!("{:<width$}  {}", app.name, code, width = width);
+    }
+}
+
+/// Preguntar sí/no por stdin; cualquier respuesta que no sea "y" es no
+fn ask(question: &str) -> bool {
+    use std::io::Write;
+    print!("{}", question);
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).is_ok() && answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// Pedir por stdin las variables de un grupo y devolverlo con sus valores.
+/// Una respuesta vacía toma el valor por defecto; `None` si falta alguna
+/// obligatoria o no se pudo leer stdin
+fn ask_prompts(group: &Group) -> Option<Group> {
+    use std::io::Write;
+    let mut values = HashMap::new();
+    for prompt in &group.prompts {
+        print!("{}: ", prompt.question());
+        let _ = io::stdout().flush();
+        let mut answer = String::new();
+        if io::stdin().read_line(&mut answer).ok()? == 0 {
+            return None;
+        }
+        let answer = answer.trim_end_matches(['\r', '\n']);
+        let value = match (answer.is_empty(), &prompt.default) {
+            (true, Some(default)) => default.clone(),
+            (true, None) => return None,
+            (false, _) => answer.to_string(),
+        };
+        values.insert(prompt.name.clone(), value);
+    }
+    Some(group.with_values(&values))
+}
+
+/// Grupo con las variables en su valor por defecto, para lanzamientos sin
+/// nadie a quien preguntar (`serve`, `schedule`)
+#[cfg(unix)]
+fn with_defaults(group: &Group) -> Result<Group, String> {
+    let values = group.prompt_defaults();
+    match group.missing_prompt(&values) {
+        Some(prompt) => Err(format!(
+            "group '{}' needs a value for {{{{{}}}}}",
+            group.name, prompt.name
+        )),
+        None => Ok(group.with_values(&values)),
+    }
+}
+
+/// Marca para grupos y apps deshabilitados en listados
+fn disabled_suffix(enabled: bool) -> &'static str {
+    if enabled {
+        ""
+    } else {
+        " (disabled)"
+    }
+}
+
+/// `launcher doctor`: cada ubicación en la que se busca la configuración, en
+/// orden, si existe y se puede leer, cuál se usaría y si carga sin errores
+fn doctor(explicit: Option<&PathBuf>, prefer_global: bool) {
+    let state = |path: &Path| match std::fs::File::open(path) {
+        Ok(_) => "found, readable",
+        Err(_) if path.exists() => "found, NOT readable",
+        Err(_) => "missing",
+    };
+    let env = std::env::var_os(launcher::CONFIG_ENV).filter(|v| !v.is_empty());
+    let xdg = std::env::var_os("XDG_CONFIG_HOME").filter(|v| !v.is_empty());
+
+    let cwd = std::env::current_dir().map_or_else(|e| e.to_string(), |d| d.display().to_string());
+    println!("working directory: {}", cwd);
+    println!(
+        "config directory:  {} ({})",
+        dirs::config_dir().map_or("none".to_string(), |d| d.display().to_string()),
+        match &xdg {
+            Some(dir) => format!("XDG_CONFIG_HOME={}", dir.to_string_lossy()),
+            None => "XDG_CONFIG_HOME not set".to_string(),
+        }
+    );
+    println!();
+
+    // Mismo orden que `Config::load_from`: --config, la variable y la búsqueda
+    let mut chosen = None;
+    match explicit {
+        Some(path) => {
+            println!("--config:        {}  [{}]", path.display(), state(path));
+            chosen = Some(path.clone());
+        }
+        None => println!("--config:        not given"),
+    }
+    match env.as_ref().map(|v| expand_path(&v.to_string_lossy())) {
+        Some(path) => {
+            let note = if chosen.is_some() { "  (ignored: --config wins)" } else { "" };
+            println!("{}: {}  [{}]{}", launcher::CONFIG_ENV, path.display(), state(&path), note);
+            chosen = chosen.or(Some(path));
+        }
+        None => println!("{}: not set", launcher::CONFIG_ENV),
+    }
+    println!();
+
+    let searched = chosen.is_none();
+    println!(
+        "search order{}:",
+        if searched { "" } else { " (not used: a path was given)" }
+    );
+    for (i, path) in launcher::search_paths(prefer_global).iter().enumerate() {
+        let pick = chosen.is_none() && path.exists();
+        println!(
+            "  {:>2}. {}  [{}]{}",
+            i + 1,
+            path.display(),
+            state(path),
+            if pick { "  <- chosen" } else { "" }
+        );
+        if pick {
+            chosen = Some(path.clone());
+        }
+    }
+    if let Some(dir) = launcher::conf_d_dir().filter(|_| searched) {
+        let note = if dir.is_dir() { "fragments are merged in" } else { "missing" };
+        println!("  conf.d: {}  [{}]", dir.display(), note);
+    }
+    println!();
+
+    match Config::load_validated(explicit, prefer_global) {
+        Ok((config, path)) => println!(
+            "result: {} loads fine ({} group(s), {} warning(s))",
+            path.display(),
+            config.groups.len(),
+            config.warnings.len()
+        ),
+        Err(e) => println!("result: {}", e),
+    }
+}
+
+/// Argumentos de una app separados por espacios, como en `list --verbose` y
+/// la vista previa. Los archivos aparecen como `"$(cat 'ruta')"`
+fn args_line(args: &[Arg]) -> String {
+    args.iter().map(Arg::to_string).collect::<Vec<_>>().join(" ")
+}
+
+/// Imprimir los grupos (y opcionalmente sus apps) en stdout.
+/// Los subgrupos se indentan bajo su grupo padre
+fn print_groups(groups: &[Group], verbose: bool, depth: usize) {
+    let indent = "  ".repeat(depth);
+    for group in groups {
+        let aliases = if group.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" ({})", group.aliases.join(", "))
+        };
+        println!("{}{}{}{}", indent, group.name, aliases, disabled_suffix(group.enabled));
+        if verbose {
+            for app in &group.apps {
+                let suffix = disabled_suffix(app.enabled);
+                if app.args.is_empty() {
+                    println!("{}    {}: {}{}", indent, app.name, app.command, suffix);
+                } else {
+                    println!(
+                        "{}    {}: {} {}{}",
+                        indent,
+                        app.name,
+                        app.command,
+                        args_line(&app.args),
+                        suffix
+                    );
+                }
+            }
+        }
+        print_groups(&group.subgroups, verbose, depth + 1);
+    }
+}
+
+/// Grupo tal como lo imprime `list --output json`, para scripts
+#[derive(Serialize)]
+struct GroupListing<'a> {
+    name: &'a str,
+    aliases: &'a [String],
+    enabled: bool,
+    app_count: usize,
+    apps: Vec<AppListing<'a>>,
+    subgroups: Vec<GroupListing<'a>>,
+}
+
+/// App dentro de un `GroupListing`
+#[derive(Serialize)]
+struct AppListing<'a> {
+    name: &'a str,
+    command: &'a str,
+    args: &'a [Arg],
+    enabled: bool,
+    use_shell: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cwd: Option<&'a str>,
+}
+
+impl<'a> GroupListing<'a> {
+    fn new(group: &'a Group) -> Self {
+        let apps = group
+            .apps
+            .iter()
+            .map(|app| AppListing {
+                name: &app.name,
+                command: &app.command,
+                args: &app.args,
+                enabled: app.enabled,
+                use_shell: app.use_shell,
+                cwd: app.cwd.as_deref(),
+            })
+            .collect();
+        GroupListing {
+            name: &group.name,
+            aliases: &group.aliases,
+            enabled: group.enabled,
+            app_count: group.apps.len(),
+            apps,
+            subgroups: group.subgroups.iter().map(GroupListing::new).collect(),
+        }
+    }
+}
+
+// ============================================================================
+// SERVE - Control por socket Unix
+// ============================================================================
+/// Escuchar en el socket de control y atender cada conexión en su propio
+/// hilo. Protocolo de líneas: `list`, `run <group>` y `reload`. Cada
+/// respuesta termina con una línea `ok` / `ok: ...` o `error: ...`.
+/// Mientras tanto, los grupos con `schedule` se lanzan a su hora
+#[cfg(unix)]
+fn serve(config: Config, explicit_config: Option<PathBuf>, prefer_global: bool) -> io::Result<()> {
+    use std::os::unix::net::{UnixListener, UnixStream};
+    use std::sync::{Arc, Mutex};
+
+    let socket = config.socket_path();
+    if socket.exists() {
+        // Un socket que no acepta conexiones quedó de una ejecución anterior
+        if UnixStream::connect(&socket).is_ok() {
+            return Err(io::Error::new(
+                io::ErrorKind::AddrInUse,
+                format!("another launcher is already serving on {}", socket.display()),
+            ));
+        }
+        std::fs::remove_file(&socket)?;
+    }
+    if let Some(dir) = socket.parent() {
+        std::fs::create_dir_all(dir)?;
+    }
+    let listener = UnixListener::bind(&socket)?;
+    eprintln!("Listening on {}", socket.display());
+
+    let config = Arc::new(Mutex::new(config));
+    let scheduled = Arc::clone(&config);
+    std::thread::spawn(move || run_scheduler(&scheduled));
+
+    let explicit_config = Arc::new(explicit_config);
+    for stream in listener.incoming() {
+        let Ok(stream) = stream else { continue };
+        let config = Arc::clone(&config);
+        let explicit_config = Arc::clone(&explicit_config);
+        std::thread::spawn(move || {
+            use std::io::{BufRead, BufReader, Write};
+            let mut writer = &stream;
+            for line in BufReader::new(&stream).lines() {
+                let Ok(line) = line else { break };
+                let reply =
+                    control_command(line.trim(), &config, &explicit_config, prefer_global);
+                if writeln!(writer, "{}", reply).is_err() {
+                    break;
+                }
+            }
+        });
+    }
+    Ok(())
+}
+
+/// Cada cuánto revisa el planificador si algún `schedule` está pendiente
+#[cfg(unix)]
+const SCHEDULER_TICK: Duration = Duration::from_secs(1);
+
+/// Lanzar los grupos con `schedule` cuando les toca. La próxima ejecución se
+/// calcula desde el arranque (o desde que cambia la expresión tras un
+/// `reload`), así que las que caen con el proceso parado no se recuperan
+#[cfg(unix)]
+fn run_scheduler(config: &std::sync::Mutex<Config>) {
+    let mut next: HashMap<String, (String, chrono::DateTime<chrono::Local>)> = HashMap::new();
+    loop {
+        let now = chrono::Local::now();
+        let mut due = Vec::new();
+        let opts = {
+            let config = config.lock().unwrap_or_else(|e| e.into_inner());
+            let groups = flatten_groups(&config.groups);
+            next.retain(|key, _| groups.iter().any(|(k, _)| k == key));
+            for (key, group) in groups {
+                let Some(expr) = group.schedule.clone() else {
+                    continue;
+                };
+                // La configuración ya se validó, así que no debería fallar
+                let Ok(schedule) = launcher::parse_schedule(&expr) else {
+                    continue;
+                };
+                let pending = next.get(&key).filter(|(prev, _)| *prev == expr).map(|(_, at)| *at);
+                if pending.is_some_and(|at| at > now) {
+                    continue;
+                }
+                if pending.is_some() {
+                    due.push((key.clone(), group));
+                }
+                // Grupo nuevo, expresión cambiada o recién lanzado: la
+                // siguiente hora se cuenta desde ahora
+                match schedule.after(&now).next() {
+                    Some(at) => next.insert(key, (expr, at)),
+                    None => next.remove(&key),
+                };
+            }
+            LaunchOptions::from_config(&config)
+        };
+        for (key, group) in due {
+            let group = match with_defaults(&group) {
+                Ok(group) => group,
+                Err(e) => {
+                    eprintln!("[schedule] {}: skipped: {}", key, e);
+                    continue;
+                }
+            };
+            let opts = opts.clone();
+            std::thread::spawn(move || {
+                let results = launch_group_with(&group, &opts);
+                eprintln!("[schedule] {}: {}", key, summarize_launch(&group, &results));
+            });
+        }
+        std::thread::sleep(SCHEDULER_TICK);
+    }
+}
+
+/// Ejecutar una orden del socket de control y devolver la respuesta.
+/// La configuración solo se bloquea mientras se lee o se reemplaza, no
+/// durante el lanzamiento
+#[cfg(unix)]
+fn control_command(
+    line: &str,
+    config: &std::sync::Mutex<Config>,
+    explicit_config: &Option<PathBuf>,
+    prefer_global: bool,
+) -> String {
+    let (command, arg) = match line.split_once(char::is_whitespace) {
+        Some((command, arg)) => (command, arg.trim()),
+        None => (line, ""),
+    };
+    let lock = || config.lock().unwrap_or_else(|e| e.into_inner());
+
+    match command {
+        "list" => {
+            let config = lock();
+            let mut reply: Vec<String> = flatten_groups(&config.groups)
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect();
+            reply.push("ok".to_string());
+            reply.join("\n")
+        }
+        "run" if arg.is_empty() => "error: run requires a group name".to_string(),
+        "run" => {
+            let (group, opts) = {
+                // Se acepta tanto la ruta que devuelve `list` como el nombre suelto
+                let config = lock();
+                let found = flatten_groups(&config.groups)
+                    .into_iter()
+                    .find(|(key, _)| key == arg)
+                    .map(|(_, g)| g)
+                    .or_else(|| find_group(&config.groups, arg, false).cloned());
+                match found {
+                    Some(g) if !g.enabled => {
+                        return format!("error: group '{}' is disabled", g.name)
+                    }
+                    Some(g) => match with_defaults(&g.launchable()) {
+                        Ok(g) => (g, LaunchOptions::from_config(&config)),
+                        Err(e) => return format!("error: {}", e),
+                    },
+                    None => return format!("error: no group named '{}'", arg),
+                }
+            };
+            let mut results = launch_group_with(&group, &opts);
+            let summary = summarize_launch(&group, &results);
+            if results.iter_mut().all(launch_succeeded) {
+                format!("ok: {}", summary)
+            } else {
+                format!("error: {}", summary)
+            }
+        }
+        "reload" => match Config::load_validated(explicit_config.as_ref(), prefer_global) {
+            Ok((new, _)) => {
+                let groups = new.groups.len();
+                *lock() = new;
+                format!("ok: {} groups", groups)
+            }
+            Err(e) => format!("error: {}", e),
+        },
+        "" => "error: empty command".to_string(),
+        _ => format!("error: unknown command '{}' (expected list, run, reload)", command),
+    }
+}
+
+// ============================================================================
+// FUNCIÓN main
+// ============================================================================
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let cli = match Cli::parse(std::env::args().skip(1)) {
+        Ok(cli) => cli,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: launcher [-V|--version] [-c|--config <path>] [--dry-run] [--watch] [--no-color] [--prefer-global] [--force] [--attach] [list [--verbose] [--output text|json] | run <group> [--ignore-case] | run-all | dump [--format json|yaml|toml] | serve | schema | doctor]");
+            std::process::exit(2);
+        }
+    };
+
+    if cli.command == CliCommand::Version {
+        println!("launcher {}", version());
+        return Ok(());
+    }
+
+    // El schema no depende de ninguna configuración
+    if cli.command == CliCommand::Schema {
+        let schema = schemars::schema_for!(Config);
+        println!("{}", serde_json::to_string_pretty(&schema)?);
+        return Ok(());
+    }
+
+    // El diagnóstico tiene que funcionar justamente cuando no se encuentra
+    if cli.command == CliCommand::Doctor {
+        doctor(cli.config.as_ref(), cli.prefer_global);
+        return Ok(());
+    }
+
+    // Cargar configuración (ruta explícita, $LAUNCHER_CONFIG o ubicaciones estándar)
+    if let Some(path) = &cli.config {
+        if !path.exists() {
+            eprintln!("Error: config file not found: {}", path.display());
+            std::process::exit(1);
+        }
+    }
+    // Con un archivo grande en un disco de red la carga se nota: dar señales
+    // de vida antes de que la TUI ocupe la pantalla
+    let tui = matches!(cli.command, CliCommand::Tui);
+    if tui && io::stderr().is_terminal() {
+        eprintln!("Loading config...");
+    }
+    let loaded = Config::load_from(cli.config.as_ref(), cli.prefer_global);
+    let (config, config_path) = loaded.unwrap_or_else(|e| {
+        eprintln!("Error: {}", e);
+        std::process::exit(1);
+    });
+
+    for warning in &config.warnings {
+        eprintln!("Warning: {}", warning);
+    }
+
+    // No lanzar nada con una configuración inválida
+    if let Err(problems) = config.validate() {
+        eprintln!("Error: invalid config:");
+        for problem in &problems {
+            eprintln!("  - {}", problem);
+        }
+        std::process::exit(1);
+    }
+
+    // La TUI taparía los avisos: esperar a que se lean, salvo con --force
+    if tui && !config.warnings.is_empty() && !cli.force && io::stdin().is_terminal() {
+        eprint!("Press Enter to continue (--force skips this pause)...");
+        let mut answer = String::new();
+        let _ = io::stdin().read_line(&mut answer);
+    }
+
+    match &cli.command {
+        CliCommand::Tui => {}
+        CliCommand::Dump { format } => {
+            match config.serialize(format) {
+                Ok(text) => print!("{}", text),
+                Err(e) => {
+                    eprintln!("Error: cannot write config as {}: {}", format, e);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        CliCommand::List { json: true, .. } => {
+            let listing: Vec<GroupListing> = config.groups.iter().map(GroupListing::new).collect();
+            match serde_json::to_string_pretty(&listing) {
+                Ok(text) => println!("{}", text),
+                Err(e) => {
+                    eprintln!("Error: cannot write groups as JSON: {}", e);
+                    std::process::exit(1);
+                }
+            }
+            return Ok(());
+        }
+        CliCommand::List { verbose, .. } => {
+            if *verbose {
+                let path = std::fs::canonicalize(&config_path).unwrap_or(config_path);
+                println!("# config: {}", path.display());
+            }
+            print_groups(&config.groups, *verbose, 0);
+            return Ok(());
+        }
+        CliCommand::Run { group, ignore_case } => {
+            let found = find_group(&config.groups, group, *ignore_case);
+            if let Some(group) = found.filter(|g| !g.enabled) {
+                eprintln!("Error: group '{}' is disabled", group.name);
+                std::process::exit(1);
+            }
+            match found.map(Group::launchable) {
+                Some(group) if cli.dry_run => {
+                    // Las variables sin valor por defecto se muestran como `{{name}}`
+                    let group = group.with_values(&group.prompt_defaults());
+                    let opts = LaunchOptions::from_config(&config);
+                    for line in group_commands(&group, &opts) {
+                        println!("{}", line);
+                    }
+                }
+                Some(group) => {
+                    let Some(group) = ask_prompts(&group) else {
+                        eprintln!("Error: a value is required for every prompt without a default");
+                        std::process::exit(1);
+                    };
+                    let opts = LaunchOptions {
+                        capture_output: true,
+                        ..LaunchOptions::from_config(&config)
+                    };
+                    let mut results = launch_group_with(&group, &opts);
+                    for (app, res) in group.apps.iter().zip(&results) {
+                        if let Err(e) = res {
+                            eprintln!("Error al ejecutar {}: {}", app.name, e);
+                        }
+                    }
+                    // Las tareas `oneshot` se esperan aquí: el proceso termina
+                    // enseguida. Si fallan se muestra el final de su salida
+                    for (app, res) in group.apps.iter().zip(results.iter_mut()) {
+                        let (true, Ok(child)) = (app.oneshot, res) else {
+                            continue;
+                        };
+                        let output = OutputTail::capture(child);
+                        let failed = !child.wait().is_ok_and(|s| s.success());
+                        if let Some(output) = output.filter(|_| failed) {
+                            output.finish();
+                            eprintln!("--- last output of {} ---", app.name);
+                            for line in output.lines() {
+                                eprintln!("{}", line);
+                            }
+                        }
+                    }
+                    if group.apps.iter().any(|a| a.wait || a.oneshot) {
+                        print_exit_codes(&group, &mut results);
+                    }
+                    if !results.iter_mut().all(launch_succeeded) {
+                        std::process::exit(1);
+                    }
+                }
+                None => {
+                    eprintln!("Error: no group named '{}'. Available groups:", group);
+                    for g in &config.groups {
+                        eprintln!("  {}", g.name);
+                    }
+                    std::process::exit(2);
+                }
+            }
+            return Ok(());
+        }
+        CliCommand::RunAll => {
+            run_all(&config, cli.dry_run);
+            return Ok(());
+        }
+        CliCommand::Serve => {
+            #[cfg(unix)]
+            if let Err(e) = serve(config, cli.config, cli.prefer_global) {
+                eprintln!("Error: {}", e);
+                std::process::exit(1);
+            }
+            #[cfg(not(unix))]
+            {
+                eprintln!("Error: serve is only supported on Unix");
+                std::process::exit(1);
+            }
+            return Ok(());
+        }
+        CliCommand::Schema | CliCommand::Version | CliCommand::Doctor => {
+            unreachable!("handled before loading the config")
+        }
+    }
+
+    if config.groups.is_empty() {
+        eprintln!("Error: No groups configured in config file");
+        return Ok(());
+    }
+
+    // Si algo entra en pánico, restaurar la terminal antes de imprimir el
+    // mensaje; si no, queda en modo raw y en la pantalla alternativa
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen)?;
+    set_mouse_capture(config.mouse)?;
+
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    let mut app = App::new(config, config_path, cli.config.clone());
+    app.dry_run = cli.dry_run;
+    app.prefer_global = cli.prefer_global;
+    if cli.attach {
+        app.attach_log = Some(OutputTail::new(ATTACH_LOG_LINES));
+    }
+    // https://no-color.org: cualquier valor no vacío desactiva los colores
+    app.no_color = cli.no_color || std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+    if cli.watch {
+        // conf.d solo participa cuando la ruta salió de la búsqueda estándar
+        let searched = cli.config.is_none()
+            && std::env::var_os(launcher::CONFIG_ENV).is_none_or(|v| v.is_empty());
+        match ConfigWatcher::new(&app.config_path, searched) {
+            Ok(watcher) => app.watcher = Some(watcher),
+            Err(e) => {
+                let msg = format!("Cannot watch config: {}", e);
+                app.set_status(StatusMessage::new(msg, true));
+            }
+        }
+    }
+    app.restore_state(&SavedState::load());
+    let res = run_app(&mut terminal, &mut app);
+    app.saved_state().save();
+    // Con --attach las apps son parte de la sesión: se cierran con ella
+    if app.attach_log.is_some() {
+        app.stop_all();
+    }
+
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        eprintln!("Error: {}", err);
+    }
+
+    Ok(())
+}
+
+/// Activar o desactivar la captura del ratón (opción `mouse`)
+fn set_mouse_capture(enabled: bool) -> io::Result<()> {
+    if enabled {
+        execute!(io::stdout(), EnableMouseCapture)
+    } else {
+        execute!(io::stdout(), DisableMouseCapture)
+    }
+}
+
+/// Salir del modo raw y de la pantalla alternativa ignorando errores,
+/// para usarlo desde el hook de pánico
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show);
+}
+
+// ============================================================================
+// FUNCIÓN run_app
+// ============================================================================
+fn run_app(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+) -> io::Result<()> {
+    // Solo se redibuja cuando algo cambió, para no gastar CPU en reposo
+    let mut redraw = true;
+    loop {
+        if let Some((key, group)) = app.foreground.pop_front() {
+            run_interactive(terminal, app, key, group)?;
+            redraw = true;
+        }
+        redraw |= app.poll_events();
+        redraw |= app.poll_processes();
+        redraw |= app.expire_status();
+        redraw |= app.poll_watcher();
+        redraw |= app.poll_output();
+        // El spinner se anima mientras haya un lanzamiento en curso
+        redraw |= app.progress.is_some();
+        // Con `L` se sale recién cuando terminó de lanzarse todo, para no
+        // cortar un grupo secuencial a la mitad
+        if app.quit_when_done
+            && !app.launching()
+            && app.foreground.is_empty()
+            && app.after_launch.is_empty()
+        {
+            return Ok(());
+        }
+        if redraw {
+            terminal.draw(|f| ui(f, app))?;
+            redraw = false;
+        }
+
+        if crossterm::event::poll(app.config.poll_interval())? {
+            // Cualquier evento (tecla, clic, cambio de tamaño) redibuja,
+            // salvo el simple movimiento del ratón
+            let event = event::read()?;
+            redraw = !matches!(event, Event::Mouse(m) if m.kind == MouseEventKind::Moved);
+            if let Event::Mouse(mouse) = event {
+                app.handle_mouse(mouse);
+                continue;
+            }
+            if let Event::Key(key) = event {
+                // Con la ayuda abierta cualquier tecla la cierra
+                if app.show_help {
+                    app.show_help = false;
+                    continue;
+                }
+
+                // Salir con apps en marcha: las apps siguen corriendo igual,
+                // pero se pregunta por si `q` se pulsó sin querer
+                if app.confirm_quit {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => return Ok(()),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                            app.confirm_quit = false
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // Mientras se buscan copias en marcha solo se puede cancelar
+                if app.checking.is_some() {
+                    if key.code == KeyCode::Esc {
+                        app.cancel_launch();
+                    }
+                    continue;
+                }
+
+                // Con una confirmación pendiente solo se acepta y/n
+                if app.confirming() {
+                    match key.code {
+                        KeyCode::Char('y') | KeyCode::Char('Y') => app.confirm_launch(),
+                        KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
+                            app.cancel_launch()
+                        }
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                // En modo búsqueda las teclas editan el filtro
+                if app.searching {
+                    match key.code {
+                        KeyCode::Esc => app.commit_search(),
+                        KeyCode::Backspace => app.pop_query(),
+                        KeyCode::Enter => {
+                            app.select();
+                            app.clear_search();
+                        }
+                        KeyCode::Down => app.next(),
+                        KeyCode::Up => app.prev(),
+                        KeyCode::Char(c) => app.push_query(c),
+                        _ => {}
+                    }
+                    continue;
+                }
+
+                if app.output_popup.is_some() {
+                    app.output_key(key.code);
+                    continue;
+                }
+
+                // Con el formulario de variables abierto las teclas son su texto
+                if app.prompt_form.is_some() {
+                    app.prompt_key(key.code);
+                    continue;
+                }
+
+                // Con la paleta abierta las teclas van a su filtro
+                if app.palette.is_some() {
+                    app.palette_key(key.code);
+                    continue;
+                }
+
+                // Lanzar y salir: `L` o Shift+Enter (si la terminal lo distingue)
+                let shift_enter =
+                    key.code == KeyCode::Enter && key.modifiers.contains(KeyModifiers::SHIFT);
+                if shift_enter || key.code == KeyCode::Char('L') {
+                    app.select_and_quit();
+                    continue;
+                }
+
+                let ctrl_p = key.code == KeyCode::Char('p')
+                    && key.modifiers.contains(KeyModifiers::CONTROL);
+                if ctrl_p || key.code == KeyCode::Char(':') {
+                    app.open_palette();
+                    continue;
+                }
+
+                // Con una búsqueda confirmada, n/N recorren las coincidencias y
+                // Esc quita el filtro en lugar de salir
+                if !app.query.is_empty() {
+                    match key.code {
+                        KeyCode::Char('n') => {
+                            app.next_match(true);
+                            continue;
+                        }
+                        KeyCode::Char('N') => {
+                            app.next_match(false);
+                            continue;
+                        }
+                        KeyCode::Esc => {
+                            app.clear_search();
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+
+                // Primero las acciones configurables, luego las teclas fijas
+                if let Some(action) = app.config.keybindings.action(key.code) {
+                    match action {
+                        Action::Quit if app.running_count() > 0 => app.confirm_quit = true,
+                        Action::Quit => return Ok(()),
+                        Action::Down => app.next(),
+                        Action::Up => app.prev(),
+                        // Lanzar y seguir en el launcher; el resultado
+                        // se muestra un momento en el footer
+                        Action::Select => app.select(),
+                    }
+                    continue;
+                }
+
+                match key.code {
+                    KeyCode::Char('/') => {
+                        app.start_search();
+                    }
+                    KeyCode::Char('?') => {
+                        app.show_help = true;
+                    }
+                    KeyCode::Char('A') => {
+                        app.select_all();
+                    }
+                    KeyCode::Char(c @ '1'..='9') => {
+                        app.quick_select(c as usize - '0' as usize);
+                    }
+                    KeyCode::Char('r') => {
+                        app.reload();
+                    }
+                    KeyCode::Char('e') => {
+                        edit_config(terminal, app)?;
+                    }
+                    KeyCode::Char('x') => {
+                        app.stop_selected();
+                    }
+                    KeyCode::Char('o') => {
+                        app.show_output();
+                    }
+                    KeyCode::Char('m') => {
+                        app.toggle_messages();
+                    }
+                    KeyCode::Char('[') if app.show_messages => {
+                        app.scroll_messages(true);
+                    }
+                    KeyCode::Char(']') if app.show_messages => {
+                        app.scroll_messages(false);
+                    }
+                    KeyCode::Right => {
+                        app.expand();
+                    }
+                    KeyCode::Char('h') => {
+                        app.move_column(false);
+                    }
+                    KeyCode::Char('l') => {
+                        app.move_column(true);
+                    }
+                    KeyCode::Backspace | KeyCode::Left => {
+                        app.ascend();
+                    }
+                    KeyCode::Char('g') | KeyCode::Home => {
+                        app.first();
+                    }
+                    KeyCode::Char('G') | KeyCode::End => {
+                        app.last();
+                    }
+                    KeyCode::PageDown => {
+                        app.page(true);
+                    }
+                    KeyCode::PageUp => {
+                        app.page(false);
+                    }
+                    KeyCode::Tab => {
+                        app.switch_tab(true);
+                    }
+                    KeyCode::BackTab => {
+                        app.switch_tab(false);
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+}
+
+/// Editor para `e`: `$EDITOR` (puede incluir argumentos, p. ej. `code -w`),
+/// o `vi` (`notepad` en Windows) si no está definido
+fn editor_command() -> (String, Vec<String>) {
+    let editor = std::env::var("EDITOR")
+        .ok()
+        .filter(|e| !e.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "notepad" } else { "vi" }.to_string());
+    let mut parts = editor.split_whitespace().map(String::from);
+    let program = parts.next().unwrap_or_default();
+    (program, parts.collect())
+}
+
+/// Abrir el archivo de configuración en el editor. La TUI se suspende
+/// mientras el editor usa la terminal y al volver se recarga la configuración
+fn edit_config(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+) -> io::Result<()> {
+    let (program, args) = editor_command();
+
+    restore_terminal();
+    let status = std::process::Command::new(&program)
+        .args(&args)
+        .arg(&app.config_path)
+        .status();
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    set_mouse_capture(app.config.mouse)?;
+    terminal.clear()?;
+
+    match status {
+        Ok(status) if status.success() => app.reload(),
+        Ok(status) => {
+            let msg = format!("{} exited with {}; config not reloaded", program, status);
+            app.set_status(StatusMessage::new(msg, true));
+        }
+        Err(e) => {
+            let msg = format!("Cannot start editor {}: {}", program, e);
+            app.set_status(StatusMessage::new(msg, true));
+        }
+    }
+    Ok(())
+}
+
+/// Correr una app `interactive` en primer plano. Como con el editor, la TUI
+/// se suspende (sin modo raw ni pantalla alternativa, y con la pantalla
+/// limpia) hasta que la app termina, y después se vuelve a dibujar
+fn run_interactive(
+    terminal: &mut Terminal<CrosstermBackend<io::Stdout>>,
+    app: &mut App,
+    key: String,
+    group: Group,
+) -> io::Result<()> {
+    let cmd = &group.apps[0];
+    let opts = LaunchOptions {
+        attach: false,
+        capture_output: false,
+        ..app.launch_options()
+    };
+
+    restore_terminal();
+    let _ = execute!(
+        io::stdout(),
+        crossterm::terminal::Clear(crossterm::terminal::ClearType::All),
+        crossterm::cursor::MoveTo(0, 0)
+    );
+    let res = execute_command(cmd, &opts).and_then(|mut child| {
+        // La app comparte el grupo de procesos de la terminal: sin esto un
+        // Ctrl-C en ella también mataría al launcher, sin guardar el estado
+        // ni parar las apps de `--attach`. Como `system(3)`, el launcher
+        // ignora SIGINT y SIGQUIT solo mientras espera (la app ya arrancó
+        // con los manejadores normales)
+        let _ignored = IgnoreInterrupts::new();
+        child.wait().map(|status| (child, status))
+    });
+    enable_raw_mode()?;
+    execute!(io::stdout(), EnterAlternateScreen)?;
+    set_mouse_capture(app.config.mouse)?;
+    terminal.clear()?;
+
+    let (text, error) = match &res {
+        Ok((_, status)) if status.success() => (format!("{} exited", cmd.name), false),
+        Ok((_, status)) => (format!("{} exited with {}", cmd.name, status), true),
+        Err(e) => (format!("Failed to launch {}: {}", cmd.name, e), true),
+    };
+    app.set_status(StatusMessage::new(text, error));
+    if res.is_ok() {
+        app.launched.insert(key.clone(), chrono::Utc::now().timestamp());
+        app.remember_recent(&key);
+    }
+    let tracked = TrackedApp::new(&group, cmd, res.map(|(child, _)| child));
+    app.processes.entry(key).or_default().push(tracked);
+    Ok(())
+}
+
+/// SIGINT y SIGQUIT ignorados mientras exista; al soltarlo vuelven los
+/// manejadores anteriores. En Windows no hace nada
+struct IgnoreInterrupts {
+    #[cfg(unix)]
+    previous: [(libc::c_int, libc::sighandler_t); 2],
+}
+
+impl IgnoreInterrupts {
+    fn new() -> Self {
+        #[cfg(unix)]
+        {
+            let ignore = |sig| (sig, unsafe { libc::signal(sig, libc::SIG_IGN) });
+            IgnoreInterrupts {
+                previous: [ignore(libc::SIGINT), ignore(libc::SIGQUIT)],
+            }
+        }
+        #[cfg(not(unix))]
+        IgnoreInterrupts {}
+    }
+}
+
+impl Drop for IgnoreInterrupts {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        for (sig, handler) in self.previous {
+            unsafe { libc::signal(sig, handler) };
+        }
+    }
+}
+
+// ============================================================================
 // FUNCIÓN ui
 // ============================================================================
-fn ui(f: &mut ratatui::Frame, app: &App) {
+fn ui(f: &mut ratatui::Frame, app: &mut App) {
     let size = f.area();
 
     let chunks = Layout::default()
@@ -253,53 +3443,1248 @@ fn ui(f: &mut ratatui::Frame, app: &App) {
             [
                 Constraint::Length(3),
                 Constraint::Min(10),
-                Constraint::Length(3),
+                Constraint::Length(2),
             ]
             .as_ref(),
         )
         .split(size);
 
     // Título
+    let theme = &app.theme();
     let title = Paragraph::new("What are you going to do today?")
-        .style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD))
+        .style(Style::default().fg(theme.accent()).add_modifier(Modifier::BOLD))
         .alignment(Alignment::Center);
     f.render_widget(title, chunks[0]);
 
+    // Pestañas de categorías, solo si algún grupo tiene categoría
+    let categories = app.categories();
+    if !categories.is_empty() {
+        let titles: Vec<String> = std::iter::once("All")
+            .chain(categories)
+            .map(|c| format!(" {} ", c))
+            .collect();
+        let tabs = Tabs::new(titles)
+            .select(app.tab)
+            .style(Style::default().fg(Color::White))
+            .highlight_style(
+                Style::default()
+                    .fg(theme.selected_fg())
+                    .bg(theme.selected_bg())
+                    .add_modifier(Modifier::BOLD),
+            )
+            .divider(Span::styled("|", Style::default().fg(theme.border())));
+        let bar = Rect { y: chunks[0].y + 2, height: 1, ..chunks[0] };
+        f.render_widget(tabs, bar);
+    }
+
     // Lista de grupos
     let items: Vec<ListItem> = app
-        .groups
+        .visible
         .iter()
         .enumerate()
-        .map(|(i, group)| {
-            let style = if i == app.selected {
+        .map(|(pos, &idx)| {
+            // Los grupos con subgrupos se marcan para indicar que se pueden abrir
+            let (name, suffix, enabled, icon) = match app.expanded {
+                Some(group_idx) => {
+                    let cmd = &app.current_groups()[group_idx].apps[idx];
+                    (&cmd.name, "", cmd.enabled, None)
+                }
+                None => {
+                    let group = &app.current_groups()[idx];
+                    let suffix = if group.subgroups.is_empty() { "" } else { " ›" };
+                    (&group.name, suffix, group.enabled, group.icon.as_deref())
+                }
+            };
+            // Los primeros nueve grupos llevan el número que los lanza
+            let number = if pos < 9 {
+                (pos + 1).to_string()
+            } else {
+                " ".to_string()
+            };
+            // Los grupos deshabilitados se muestran en gris
+            let base = if enabled {
                 Style::default()
-                    .fg(Color::Black)
-                    .bg(Color::Cyan)
-                    .add_modifier(Modifier::BOLD)
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(Color::DarkGray)
             };
-
-            let line = Line::from(Span::styled(format!("  ▸ {}", group), style));
+            let mut spans = vec![
+                Span::styled(format!(" {} ", number), Style::default().fg(Color::DarkGray)),
+                // El ancho de emojis anchos lo resuelve ratatui al dibujar
+                Span::styled(format!("{} ", icon.unwrap_or("▸")), base),
+            ];
+            spans.extend(highlight_matches(name, &app.match_positions[pos], base));
+            spans.push(Span::styled(suffix, base));
+            if !enabled {
+                spans.push(Span::styled(" (disabled)", base));
+            }
+            if app.expanded.is_none() {
+                let details = group_details(app, idx);
+                spans.push(Span::styled(details, Style::default().fg(Color::DarkGray)));
+            }
+            let line = Line::from(spans);
             ListItem::new(line)
         })
         .collect();
 
+    // Mostrar el filtro activo en el título
+    let mut list_title = String::from(" Group Apps ");
+    for name in app.breadcrumbs() {
+        list_title.push_str(&format!("› {} ", name));
+    }
+    if let Some(idx) = app.expanded {
+        list_title.push_str(&format!("› {} (apps) ", app.current_groups()[idx].name));
+    }
+    if app.searching || !app.query.is_empty() {
+        list_title.push_str(&format!("— /{} ", app.query));
+    }
+
+    // Ruta de la configuración en uso, discreta en el borde inferior
+    let config_title = Line::from(Span::styled(
+        format!(" {} ", display_path(&app.config_path)),
+        Style::default().fg(Color::DarkGray),
+    ))
+    .right_aligned();
+    // El color del borde indica el modo: al buscar, las letras filtran y no navegan
+    let border = if app.searching {
+        theme.search()
+    } else {
+        theme.border()
+    };
+    let list_block = Block::default()
+        .borders(Borders::ALL)
+        .title(list_title)
+        .title_bottom(config_title)
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .style(Style::default().fg(border));
+    let empty_message = if app.query.is_empty() {
+        "No groups to show"
+    } else {
+        "No groups match the search"
+    };
+    let highlight = Style::default()
+        .fg(theme.selected_fg())
+        .bg(theme.selected_bg())
+        .add_modifier(Modifier::BOLD);
+
+    // Con `m`, el registro de la sesión ocupa la parte de abajo
+    let main_area = if app.show_messages {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(5), Constraint::Length(MESSAGE_PANE_HEIGHT)])
+            .split(chunks[1]);
+        render_messages(f, app, split[1]);
+        split[0]
+    } else {
+        chunks[1]
+    };
+
+    // Lista a la izquierda, detalle del grupo resaltado a la derecha
+    let body = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(main_area);
+
+    // Sección "Recent" sobre la lista, con los últimos grupos lanzados
+    let recent = app.shown_recent();
+    let list_area = if recent.is_empty() {
+        body[0]
+    } else {
+        let split = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(recent.len() as u16 + 2), Constraint::Min(3)])
+            .split(body[0]);
+        render_recent(f, app, &recent, split[0]);
+        split[1]
+    };
+
+    // Render con estado para que la selección siempre quede visible,
+    // incluso si la terminal se achica con un elemento bajo seleccionado.
+    // Con el foco en "Recent" la lista se dibuja sin resaltar
+    app.list_area = list_area;
+    let inner = list_block.inner(list_area);
+    app.list_columns = column_count(app.visible.len(), inner.width, inner.height);
+    if app.visible.is_empty() {
+        // Sin grupos (config vacía tras recargar o filtro sin resultados)
+        let empty = Paragraph::new(empty_message)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center)
+            .block(list_block.padding(Padding::top(1)));
+        f.render_widget(empty, list_area);
+    } else if app.list_columns > 1 {
+        let selected = app.recent_focus.is_none().then_some(app.selected);
+        let columns = (app.list_columns, app.column_offset);
+        let (rows, offset) =
+            render_columns(f, items, list_block, highlight, list_area, columns, selected);
+        (app.column_rows, app.column_offset) = (rows, offset);
+    } else {
+        let list = List::new(items)
+            .block(list_block)
+            .style(Style::default().fg(Color::White))
+            .highlight_style(highlight);
+        if app.recent_focus.is_some() {
+            let mut state = app.list_state.clone();
+            state.select(None);
+            f.render_stateful_widget(list, list_area, &mut state);
+        } else {
+            f.render_stateful_widget(list, list_area, &mut app.list_state);
+        }
+    }
+    if app.dry_run {
+        // En dry-run el panel derecho comparte espacio con el log de comandos
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(body[1]);
+        render_preview(f, app, right[0]);
+        render_dry_run_log(f, app, right[1]);
+    } else if let Some(log) = &app.attach_log {
+        // Con --attach el panel derecho muestra también la salida de las apps
+        let right = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(body[1]);
+        let log = log.clone();
+        render_preview(f, app, right[0]);
+        render_attach_log(f, &log, right[1]);
+    } else {
+        render_preview(f, app, body[1]);
+    }
+
+    // Footer en dos líneas: progreso, resultado del último lanzamiento o las
+    // teclas de navegación arriba, y debajo las teclas del modo actual
+    let status = match (&app.progress, &app.last_result) {
+        (Some(progress), _) => Line::styled(
+            format!("{} {}", progress.spinner(), progress.text),
+            Style::default().fg(theme.accent()),
+        ),
+        (None, Some(msg)) => {
+            let color = if msg.error { Color::Red } else { Color::Green };
+            Line::styled(msg.text.as_str(), Style::default().fg(color))
+        }
+        (None, None) => {
+            let keys = &app.config.keybindings;
+            Line::styled(
+                format!(
+                    "{}, {}: Navigate  |  {}: Select  |  /: Search  |  ?: Help  |  {}: Quit",
+                    Keybindings::hint(&keys.up),
+                    Keybindings::hint(&keys.down),
+                    Keybindings::hint(&keys.select),
+                    Keybindings::hint(&keys.quit),
+                ),
+                Style::default().fg(theme.accent()),
+            )
+        }
+    };
+    let footer = Paragraph::new(vec![status, mode_hints(app)]).alignment(Alignment::Center);
+    f.render_widget(footer, chunks[2]);
+
+    // Modal de confirmación sobre la lista
+    let running = app.running_count();
+    let question = match &app.pending {
+        _ if app.confirm_quit && app.attach_log.is_some() => Some((
+            "Stop the running apps and quit?".to_string(),
+            format!("{} attached app(s) will get SIGTERM", running),
+        )),
+        _ if app.confirm_quit => Some((
+            "Apps are running; quit anyway?".to_string(),
+            format!("{} app(s) launched from here are still running", running),
+        )),
+        Some(PendingLaunch::Group(idx)) if app.pending_running > 0 => Some((
+            format!("Launch '{}' again?", app.current_groups()[*idx].name),
+            format!("{} matching process(es) already running", app.pending_running),
+        )),
+        Some(PendingLaunch::App { group, idx, .. }) if app.pending_running > 0 => Some((
+            format!("Launch '{}' again?", group.apps[*idx].name),
+            format!("{} matching process(es) already running", app.pending_running),
+        )),
+        Some(PendingLaunch::Group(idx)) => {
+            let group = &app.current_groups()[*idx];
+            Some((
+                format!("Launch '{}'?", group.name),
+                format!("{} app(s) will be started", group.launchable().apps.len()),
+            ))
+        }
+        Some(PendingLaunch::App { group, idx, .. }) => Some((
+            format!("Launch '{}'?", group.apps[*idx].name),
+            format!("1 app from '{}' will be started", group.name),
+        )),
+        Some(PendingLaunch::All) => {
+            let groups = flatten_groups(&app.config.groups);
+            let apps: usize = groups.iter().map(|(_, g)| g.apps.len()).sum();
+            Some((
+                "Launch all groups?".to_string(),
+                format!("{} app(s) in {} group(s) will be started", apps, groups.len()),
+            ))
+        }
+        None => None,
+    };
+    if let Some((title, detail)) = question {
+        let text = vec![
+            Line::from(title),
+            Line::from(detail),
+            Line::from(""),
+            Line::from(Span::styled(
+                "y: Yes  |  n/Esc: No",
+                Style::default().add_modifier(Modifier::BOLD),
+            )),
+        ];
+        let popup = Paragraph::new(text)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true })
+            .block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .title(" Confirm ")
+                    .border_type(ratatui::widgets::BorderType::Rounded)
+                    .style(Style::default().fg(Color::Yellow)),
+            );
+        let area = centered_rect(50, 6, size);
+        f.render_widget(Clear, area);
+        f.render_widget(popup, area);
+    }
+
+    if let Some(form) = &app.prompt_form {
+        render_prompt_form(f, form, theme, size);
+    }
+
+    if let Some(palette) = &mut app.palette {
+        render_palette(f, palette, theme, size);
+    }
+
+    if let Some(popup) = &app.output_popup {
+        render_output(f, popup, theme, size);
+    }
+
+    if app.show_help {
+        render_help(f, app, size);
+    }
+
+    if app.no_color {
+        strip_colors(f.buffer_mut());
+    }
+}
+
+/// Quitar los colores de lo ya dibujado, conservando la información que
+/// daban: un fondo (la selección) pasa a video inverso, el gris a tenue y
+/// el rojo de los errores a negrita
+fn strip_colors(buffer: &mut ratatui::buffer::Buffer) {
+    for cell in &mut buffer.content {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        match cell.fg {
+            Color::DarkGray => cell.modifier.insert(Modifier::DIM),
+            Color::Red => cell.modifier.insert(Modifier::BOLD),
+            _ => {}
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+/// Segunda línea del footer: las teclas que tienen sentido en el modo actual
+fn mode_hints(app: &App) -> Line<'static> {
+    let theme = &app.theme();
+    let hints = if app.prompt_form.is_some() {
+        "Type a value  |  Enter: Next  |  Esc: Cancel launch".to_string()
+    } else if app.confirm_quit && app.attach_log.is_some() {
+        "y: Stop apps and quit  |  n/Esc: Stay".to_string()
+    } else if app.confirm_quit {
+        "y: Quit (apps keep running)  |  n/Esc: Stay".to_string()
+    } else if app.checking.is_some() {
+        "Esc: Cancel launch".to_string()
+    } else if app.confirming() && app.pending_running > 0 {
+        "y: Launch anyway  |  n/Esc: Cancel".to_string()
+    } else if app.confirming() {
+        "y: Launch  |  n/Esc: Cancel".to_string()
+    } else if app.output_popup.is_some() {
+        "↑/↓, PgUp/PgDn: Scroll  |  Esc/o: Close".to_string()
+    } else if app.palette.is_some() {
+        "Type to filter apps  |  ↑/↓: Move  |  Enter: Run app  |  Esc: Close".to_string()
+    } else if app.searching {
+        // La búsqueda se distingue también por el color (ver el borde de la lista)
+        return Line::styled(
+            format!("/{}▏  |  Backspace: Delete  |  Enter: Launch  |  Esc: Done", app.query),
+            Style::default().fg(theme.search()),
+        );
+    } else if !app.query.is_empty() {
+        format!(
+            "n/N: Next/previous match ({}/{})  |  /: Edit search  |  Esc: Clear search",
+            (app.selected + 1).min(app.visible.len()),
+            app.visible.len()
+        )
+    } else if app.recent_focus.is_some() {
+        "Enter: Launch recent group  |  Down past the end: Back to the list".to_string()
+    } else if app.expanded.is_some() {
+        "Enter/1-9: Launch app  |  x: Stop app  |  ←/Backspace: Back to groups".to_string()
+    } else if !app.nav.is_empty() {
+        "→: Apps  |  ←/Backspace: Parent group  |  A: Launch all  |  x: Stop".to_string()
+    } else {
+        "→: Apps  |  L: Launch & quit  |  A: Launch all  |  x: Stop  |  r: Reload  |  e: Edit"
+            .to_string()
+    };
+    Line::styled(hints, Style::default().fg(Color::DarkGray))
+}
+
+/// Popup que pide la variable actual del formulario, con el avance (`1/2`)
+fn render_prompt_form(f: &mut ratatui::Frame, form: &PromptForm, theme: &Theme, size: Rect) {
+    let Some(prompt) = form.current() else {
+        return;
+    };
+    let total = form.group.prompts.len();
+    let step = format!(" {} ({}/{}) ", form.group.name, form.values.len() + 1, total);
+    let text = vec![
+        Line::from(format!("{}:", prompt.question())),
+        Line::from(vec![
+            Span::styled("> ", Style::default().fg(theme.accent())),
+            Span::raw(format!("{}▏", form.input)),
+        ]),
+        Line::from(""),
+        Line::from(Span::styled(
+            "Enter: Next  |  Esc: Cancel",
+            Style::default().add_modifier(Modifier::BOLD),
+        )),
+    ];
+    let popup = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(step)
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .style(Style::default().fg(theme.border())),
+    );
+    let area = centered_rect(50, 6, size);
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// Popup con la salida capturada de una app, desde la línea `scroll`
+fn render_output(f: &mut ratatui::Frame, popup: &OutputPopup, theme: &Theme, size: Rect) {
+    let lines = popup.output.lines();
+    let text: Vec<Line> = if lines.is_empty() {
+        vec![Line::styled("(no output)", Style::default().fg(Color::DarkGray))]
+    } else {
+        lines.into_iter().map(Line::from).collect()
+    };
+    let height = (launcher::OUTPUT_TAIL_LINES as u16 + 2).min(size.height.saturating_sub(2));
+    let area = centered_rect(80, height, size);
+    let paragraph = Paragraph::new(text)
+        .style(Style::default().fg(Color::White))
+        .scroll((popup.scroll, 0))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(popup.title.clone())
+                .border_type(ratatui::widgets::BorderType::Rounded)
+                .padding(Padding::horizontal(1))
+                .border_style(Style::default().fg(theme.border())),
+        );
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+}
+
+/// Popup de la paleta de comandos: filtro arriba y apps coincidentes debajo
+fn render_palette(f: &mut ratatui::Frame, palette: &mut Palette, theme: &Theme, size: Rect) {
+    let height = (size.height * 3 / 5).max(8);
+    let area = centered_rect(70, height, size);
+    f.render_widget(Clear, area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" Run app ")
+        .border_type(ratatui::widgets::BorderType::Rounded)
+        .padding(Padding::horizontal(1))
+        .style(Style::default().fg(theme.border()));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(2), Constraint::Min(1)])
+        .split(inner);
+
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("> ", Style::default().fg(theme.accent())),
+        Span::styled(palette.query.clone(), Style::default().fg(Color::White)),
+    ]));
+    f.render_widget(input, rows[0]);
+
+    if palette.visible.is_empty() {
+        let empty = Paragraph::new("No matching apps").style(Style::default().fg(Color::DarkGray));
+        f.render_widget(empty, rows[1]);
+        return;
+    }
+
+    let items: Vec<ListItem> = palette
+        .visible
+        .iter()
+        .zip(&palette.match_positions)
+        .map(|(&entry, positions)| {
+            let label = &palette.labels[entry];
+            let style = Style::default().fg(Color::White);
+            ListItem::new(Line::from(highlight_matches(label, positions, style)))
+        })
+        .collect();
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .fg(theme.selected_fg())
+            .bg(theme.selected_bg())
+            .add_modifier(Modifier::BOLD),
+    );
+    f.render_stateful_widget(list, rows[1], &mut palette.list_state);
+}
+
+/// Popup con todas las teclas y su acción
+fn render_help(f: &mut ratatui::Frame, app: &App, size: Rect) {
+    let keys = &app.config.keybindings;
+    let theme = &app.theme();
+    let entries = [
+        (Keybindings::hint(&keys.up), "Move up (into Recent from the top)"),
+        (Keybindings::hint(&keys.down), "Move down"),
+        (Keybindings::hint(&keys.select), "Launch group / open subgroups"),
+        ("L / Shift-Enter".to_string(), "Launch and quit"),
+        ("g / G".to_string(), "Jump to first / last (also Home / End)"),
+        ("PgUp / PgDn".to_string(), "Move one screen up / down"),
+        ("h / l".to_string(), "Previous / next column (many groups)"),
+        ("→".to_string(), "Show the group's apps to launch one"),
+        ("Backspace/←".to_string(), "Back to parent group"),
+        ("Tab/Shift-Tab".to_string(), "Next / previous category"),
+        ("1-9".to_string(), "Launch the numbered group"),
+        ("A".to_string(), "Launch all groups"),
+        ("/".to_string(), "Search groups"),
+        ("n / N".to_string(), "Next / previous match (search kept with Esc)"),
+        (": / Ctrl-P".to_string(), "Run any app (command palette)"),
+        ("r".to_string(), "Reload config"),
+        ("e".to_string(), "Edit config in $EDITOR"),
+        ("x".to_string(), "Stop selected group"),
+        ("o".to_string(), "Show the output of the last oneshot app"),
+        ("m".to_string(), "Show / hide the session log"),
+        ("[ / ]".to_string(), "Scroll the session log back / forward"),
+        ("?".to_string(), "Show this help"),
+        (Keybindings::hint(&keys.quit), "Quit"),
+    ];
+    let width = entries.iter().map(|(k, _)| k.chars().count()).max().unwrap_or(0);
+
+    let mut text: Vec<Line> = entries
+        .iter()
+        .map(|(key, action)| {
+            Line::from(vec![
+                Span::styled(
+                    format!("{:>width$}", key, width = width),
+                    Style::default().fg(theme.accent()).add_modifier(Modifier::BOLD),
+                ),
+                Span::raw(format!("  {}", action)),
+            ])
+        })
+        .collect();
+    text.push(Line::from(""));
+    text.push(Line::from(Span::styled(
+        format!("Config: {}", display_path(&app.config_path)),
+        Style::default().fg(Color::DarkGray),
+    )));
+    text.push(Line::from(Span::styled(
+        format!("launcher {}  |  Press any key to close", version()),
+        Style::default().fg(Color::DarkGray),
+    )));
+
+    let popup = Paragraph::new(text).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Help ")
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1)),
+    );
+    let area = centered_rect(60, entries.len() as u16 + 5, size);
+    f.render_widget(Clear, area);
+    f.render_widget(popup, area);
+}
+
+/// Ruta para mostrar en la interfaz: absoluta y con `~` en lugar del
+/// directorio personal
+fn display_path(path: &Path) -> String {
+    let path = std::fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    match dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        Some(rest) => format!("~/{}", rest.display()),
+        None => path.display().to_string(),
+    }
+}
+
+/// Número de apps del grupo `idx` y hace cuánto se lanzó por última vez,
+/// p. ej. " (4 apps · 2h ago)". Sin apps ni historial queda vacío
+fn group_details(app: &App, idx: usize) -> String {
+    let group = &app.current_groups()[idx];
+    let mut parts = Vec::new();
+    match group.apps.len() {
+        0 => {}
+        1 => parts.push("1 app".to_string()),
+        n => parts.push(format!("{} apps", n)),
+    }
+    if let Some(&at) = app.launched.get(&app.group_key(idx)) {
+        parts.push(relative_time(chrono::Utc::now().timestamp() - at));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(" · "))
+    }
+}
+
+/// Ancho mínimo de cada columna al repartir la lista, y máximo de columnas
+const MIN_COLUMN_WIDTH: usize = 30;
+const MAX_COLUMNS: usize = 3;
+
+/// Columnas para `count` grupos en un área interior de `width` x `height`:
+/// una mientras quepan en altura, y si no tantas como permita el ancho
+fn column_count(count: usize, width: u16, height: u16) -> usize {
+    let height = (height as usize).max(1);
+    if count <= height {
+        return 1;
+    }
+    let fit = (width as usize / MIN_COLUMN_WIDTH).clamp(1, MAX_COLUMNS);
+    fit.min(count.div_ceil(height))
+}
+
+/// Dibujar la lista repartida en columnas, de arriba abajo y luego a la
+/// derecha. `columns` es la cantidad de columnas y la primera fila visible
+/// del dibujado anterior. Todas comparten el desplazamiento vertical para
+/// que las filas queden alineadas; solo la columna de la selección la
+/// resalta. Devuelve las filas por columna y el nuevo desplazamiento
+fn render_columns(
+    f: &mut ratatui::Frame,
+    items: Vec<ListItem>,
+    block: Block,
+    highlight: Style,
+    area: Rect,
+    (columns, mut offset): (usize, usize),
+    selected: Option<usize>,
+) -> (usize, usize) {
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let rows = items.len().div_ceil(columns);
+    let height = (inner.height as usize).max(1);
+    // Mantener visible la fila seleccionada
+    if let Some(row) = selected.map(|s| s % rows) {
+        if row < offset {
+            offset = row;
+        } else if row >= offset + height {
+            offset = row + 1 - height;
+        }
+    }
+    offset = offset.min(rows.saturating_sub(height));
+
+    let areas = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints(vec![Constraint::Ratio(1, columns as u32); columns])
+        .split(inner);
+    // El desplazamiento se aplica recortando cada columna: sin selección,
+    // `List` volvería a la primera fila
+    for (column, chunk) in items.chunks(rows).enumerate() {
+        let shown = chunk.get(offset..).unwrap_or_default().to_vec();
+        let mut state = ListState::default();
+        state.select(selected.filter(|s| s / rows == column).map(|s| s % rows - offset));
+        let list = List::new(shown)
+            .style(Style::default().fg(Color::White))
+            .highlight_style(highlight);
+        f.render_stateful_widget(list, areas[column], &mut state);
+    }
+    (rows, offset)
+}
+
+/// Dibujar la sección "Recent" con los últimos grupos lanzados. Las claves
+/// de subgrupos se muestran como ruta (`Dev › Web`)
+fn render_recent(f: &mut ratatui::Frame, app: &App, recent: &[&str], area: Rect) {
+    let theme = &app.theme();
+    let now = chrono::Utc::now().timestamp();
+    let items: Vec<ListItem> = recent
+        .iter()
+        .map(|key| {
+            let mut spans = vec![Span::raw(format!(" ↺ {}", key))];
+            if let Some(&at) = app.launched.get(*key) {
+                let ago = format!(" ({})", relative_time(now - at));
+                spans.push(Span::styled(ago, Style::default().fg(Color::DarkGray)));
+            }
+            ListItem::new(Line::from(spans))
+        })
+        .collect();
     let list = List::new(items)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" Group Apps ")
+                .title(" Recent ")
                 .border_type(ratatui::widgets::BorderType::Rounded)
-                .style(Style::default().fg(Color::Cyan)),
+                .style(Style::default().fg(theme.border())),
         )
-        .style(Style::default().fg(Color::White));
+        .style(Style::default().fg(Color::White))
+        .highlight_style(
+            Style::default()
+                .fg(theme.selected_fg())
+                .bg(theme.selected_bg())
+                .add_modifier(Modifier::BOLD),
+        );
+    let mut state = ListState::default().with_selected(app.recent_focus);
+    f.render_stateful_widget(list, area, &mut state);
+}
 
-    f.render_widget(list, chunks[1]);
+/// Tiempo transcurrido en forma corta: "just now", "5m ago", "2h ago", "3d ago"
+fn relative_time(secs: i64) -> String {
+    match secs {
+        ..=59 => "just now".to_string(),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86400),
+    }
+}
 
-    // Footer
-    let footer = Paragraph::new("↑/k: Up  |  ↓/j: Down  |  Enter: Select  |  q/Esc: Quit")
-        .style(Style::default().fg(Color::White))
-        .alignment(Alignment::Center);
-    f.render_widget(footer, chunks[2]);
-}
\ No newline at end of file
+/// Dividir un nombre en spans resaltando los caracteres que coinciden
+/// con la búsqueda. El subrayado se mantiene también en la fila seleccionada
+fn highlight_matches(name: &str, positions: &[usize], base: Style) -> Vec<Span<'static>> {
+    let matched = base
+        .fg(Color::Yellow)
+        .add_modifier(Modifier::BOLD | Modifier::UNDERLINED);
+    let mut spans: Vec<Span> = Vec::new();
+    let mut current = String::new();
+    let mut current_matched = false;
+
+    for (i, c) in name.chars().enumerate() {
+        let is_match = positions.contains(&i);
+        if is_match != current_matched && !current.is_empty() {
+            let style = if current_matched { matched } else { base };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_matched = is_match;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_matched { matched } else { base };
+        spans.push(Span::styled(current, style));
+    }
+    spans
+}
+
+/// Panel con los comandos del grupo resaltado
+fn render_preview(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let theme = &app.theme();
+    let label = Style::default().fg(Color::DarkGray);
+    let mut lines: Vec<Line> = Vec::new();
+
+    // Con las apps de un grupo a la vista se muestra solo la resaltada
+    let (group_idx, only_app) = match app.expanded {
+        Some(group_idx) => (Some(group_idx), app.selected_group()),
+        None => (app.selected_group(), None),
+    };
+
+    if let Some(idx) = group_idx {
+        let group = &app.current_groups()[idx];
+        let key = app.group_key(idx);
+
+        for sub in group.subgroups.iter().filter(|_| only_app.is_none()) {
+            lines.push(Line::from(Span::styled(
+                format!("› {}", sub.name),
+                Style::default().fg(theme.accent()).add_modifier(Modifier::BOLD),
+            )));
+        }
+
+        let apps = group
+            .apps
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| only_app.is_none_or(|only| only == *i))
+            .map(|(_, cmd)| cmd);
+        for cmd in apps {
+            if !lines.is_empty() {
+                lines.push(Line::from(""));
+            }
+            let mut title = if cmd.enabled {
+                vec![Span::styled(
+                    cmd.name.clone(),
+                    Style::default().fg(Color::White).add_modifier(Modifier::BOLD),
+                )]
+            } else {
+                vec![Span::styled(format!("{} (disabled)", cmd.name), label)]
+            };
+            // Estado del proceso si se lanzó en esta sesión
+            if let Some(tracked) = app.tracked_app(&key, &cmd.name) {
+                let (mut text, color) = match &tracked.status {
+                    _ if tracked.restart_at.is_some() || tracked.restarting => {
+                        ("↻ restarting".to_string(), Color::Yellow)
+                    }
+                    // Una tarea `oneshot` se espera que termine: su fin es un éxito
+                    AppStatus::Running if cmd.oneshot => ("◌ running".to_string(), Color::Yellow),
+                    AppStatus::Running => ("● running".to_string(), Color::Green),
+                    AppStatus::Exited(Some(0)) if cmd.oneshot => {
+                        ("✓ done".to_string(), Color::Green)
+                    }
+                    AppStatus::Exited(Some(0)) => ("○ exited (0)".to_string(), Color::DarkGray),
+                    AppStatus::Exited(Some(code)) => (format!("✗ exited ({})", code), Color::Red),
+                    AppStatus::Exited(None) => ("○ killed".to_string(), Color::DarkGray),
+                    AppStatus::Failed(e) => (format!("✗ failed: {}", e), Color::Red),
+                };
+                if tracked.restarts > 0 {
+                    text.push_str(&format!(" (restarted {}×)", tracked.restarts));
+                }
+                title.push(Span::raw("  "));
+                title.push(Span::styled(text, Style::default().fg(color)));
+            }
+            lines.push(Line::from(title));
+            let field = match cmd.kind {
+                AppKind::Command => "  command: ",
+                AppKind::Url => "  url:     ",
+                AppKind::Open => "  open:    ",
+            };
+            let mut command = vec![
+                Span::styled(field, label),
+                Span::raw(cmd.command.clone()),
+            ];
+            // Marcar los programas que no se van a encontrar al lanzar
+            if missing_binary(cmd).is_some() {
+                command.push(Span::styled("  ✗ not found", Style::default().fg(Color::Red)));
+            }
+            lines.push(Line::from(command));
+            if !cmd.args.is_empty() {
+                lines.push(Line::from(vec![
+                    Span::styled("  args:    ", label),
+                    Span::raw(args_line(&cmd.args)),
+                ]));
+            }
+            if cmd.use_shell {
+                lines.push(Line::from(vec![
+                    Span::styled("  shell:   ", label),
+                    Span::raw("yes"),
+                ]));
+            }
+        }
+
+        if lines.is_empty() {
+            lines.push(Line::from(Span::styled("No apps in this group", label)));
+        }
+    }
+
+    let preview = Paragraph::new(lines).wrap(Wrap { trim: false }).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Apps ")
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .style(Style::default().fg(theme.border())),
+    );
+    f.render_widget(preview, area);
+}
+
+/// Log de comandos del modo dry-run; se muestran las últimas líneas
+fn render_dry_run_log(f: &mut ratatui::Frame, app: &App, area: Rect) {
+    let rows = area.height.saturating_sub(2) as usize;
+    let start = app.dry_run_log.len().saturating_sub(rows);
+    let lines: Vec<Line> = app.dry_run_log[start..]
+        .iter()
+        .map(|l| {
+            let style = if l.starts_with('#') {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default().fg(Color::White)
+            };
+            Line::from(Span::styled(l.clone(), style))
+        })
+        .collect();
+
+    let log = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Dry run ")
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .style(Style::default().fg(Color::Yellow)),
+    );
+    f.render_widget(log, area);
+}
+
+/// Registro de la sesión (`m`): las últimas entradas con su hora, o unas
+/// anteriores si se desplazó con `[`
+fn render_messages(f: &mut ratatui::Frame, app: &mut App, area: Rect) {
+    let rows = area.height.saturating_sub(2) as usize;
+    app.messages_scroll = app.messages_scroll.min(app.messages.len().saturating_sub(rows));
+    let end = app.messages.len() - app.messages_scroll;
+    let now = chrono::Local::now();
+    let mut lines: Vec<Line> = app
+        .messages
+        .range(end.saturating_sub(rows)..end)
+        .map(|(at, text)| {
+            let elapsed = chrono::Duration::from_std(at.elapsed()).unwrap_or_default();
+            Line::from(vec![
+                Span::styled(
+                    format!("{}  ", (now - elapsed).format("%H:%M:%S")),
+                    Style::default().fg(Color::DarkGray),
+                ),
+                Span::styled(text.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+    if lines.is_empty() {
+        lines.push(Line::styled("Nothing yet", Style::default().fg(Color::DarkGray)));
+    }
+
+    let title = match app.messages_scroll {
+        0 => " Log ".to_string(),
+        n => format!(" Log (↑{}) ", n),
+    };
+    let log = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(title)
+            .title_bottom(Line::from(" m: Hide  |  [ / ]: Scroll ").right_aligned())
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .style(Style::default().fg(app.theme().border())),
+    );
+    f.render_widget(log, area);
+}
+
+/// Líneas que se guardan de la salida combinada de `--attach`
+const ATTACH_LOG_LINES: usize = 1000;
+
+/// Colores de los prefijos del log de `--attach`, asignados por nombre de app
+const ATTACH_COLORS: [Color; 6] = [
+    Color::Cyan,
+    Color::Magenta,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::LightRed,
+];
+
+/// Salida combinada de `--attach`: las últimas líneas, cada una con el
+/// nombre de su app delante y en su color (como `docker compose up`)
+fn render_attach_log(f: &mut ratatui::Frame, log: &OutputTail, area: Rect) {
+    let entries = log.entries();
+    let rows = area.height.saturating_sub(2) as usize;
+    let width = entries.iter().map(|e| e.source.chars().count()).max().unwrap_or(0);
+    let lines: Vec<Line> = entries[entries.len().saturating_sub(rows)..]
+        .iter()
+        .map(|entry| {
+            let hash = entry.source.bytes().map(usize::from).sum::<usize>();
+            let color = ATTACH_COLORS[hash % ATTACH_COLORS.len()];
+            Line::from(vec![
+                Span::styled(
+                    format!("{:<width$} | ", entry.source, width = width),
+                    Style::default().fg(color),
+                ),
+                Span::styled(entry.text.clone(), Style::default().fg(Color::White)),
+            ])
+        })
+        .collect();
+
+    let log = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" Output ")
+            .border_type(ratatui::widgets::BorderType::Rounded)
+            .padding(Padding::horizontal(1))
+            .style(Style::default().fg(Color::DarkGray)),
+    );
+    f.render_widget(log, area);
+}
+
+/// Calcular un área centrada de `percent_x`% de ancho y `height` filas de alto
+fn centered_rect(percent_x: u16, height: u16, r: Rect) -> Rect {
+    let vertical = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Fill(1),
+            Constraint::Length(height),
+            Constraint::Fill(1),
+        ])
+        .split(r);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(vertical[1])[1]
+}
+// ============================================================================
+// TESTS
+// ============================================================================
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// App con un grupo por nombre, sin estado guardado
+    fn app_with(names: &[&str]) -> App {
+        let groups: Vec<String> = names
+            .iter()
+            .map(|n| format!(r#"{{ "name": "{}", "apps": [] }}"#, n))
+            .collect();
+        let json = format!(r#"{{ "groups": [{}] }}"#, groups.join(","));
+        let config: Config = serde_json::from_str(&json).unwrap();
+        App::new(config, PathBuf::from("launcher.json"), None)
+    }
+
+    #[test]
+    fn next_wraps_to_the_first_group() {
+        let mut app = app_with(&["a", "b", "c"]);
+        app.next();
+        app.next();
+        assert_eq!(app.selected, 2);
+        app.next();
+        assert_eq!(app.selected, 0);
+        assert_eq!(app.list_state.selected(), Some(0));
+    }
+
+    #[test]
+    fn prev_wraps_to_the_last_group() {
+        let mut app = app_with(&["a", "b", "c"]);
+        app.prev();
+        assert_eq!(app.selected, 2);
+        assert_eq!(app.list_state.selected(), Some(2));
+        app.prev();
+        assert_eq!(app.selected, 1);
+    }
+
+    #[test]
+    fn column_count_grows_with_the_width_once_groups_overflow() {
+        assert_eq!(column_count(10, 200, 20), 1);
+        assert_eq!(column_count(50, 40, 20), 1);
+        assert_eq!(column_count(50, 70, 20), 2);
+        assert_eq!(column_count(50, 200, 20), 3);
+        // Nunca más columnas de las que hacen falta
+        assert_eq!(column_count(25, 200, 20), 2);
+    }
+
+    #[test]
+    fn move_column_keeps_the_row_and_clamps_to_short_columns() {
+        let names: Vec<String> = (0..7).map(|i| i.to_string()).collect();
+        let mut app = app_with(&names.iter().map(String::as_str).collect::<Vec<_>>());
+        (app.list_columns, app.column_rows) = (3, 3);
+        app.next();
+        app.move_column(true);
+        assert_eq!(app.selected, 4);
+        app.move_column(true);
+        assert_eq!(app.selected, 6);
+        app.move_column(true);
+        assert_eq!(app.selected, 6);
+        app.move_column(false);
+        assert_eq!(app.selected, 3);
+    }
+
+    #[test]
+    fn next_and_prev_stop_at_the_ends_without_wrap_navigation() {
+        let mut app = app_with(&["a", "b", "c"]);
+        app.config.wrap_navigation = false;
+        app.prev();
+        assert_eq!(app.selected, 0);
+        app.last();
+        app.next();
+        assert_eq!(app.selected, 2);
+        assert_eq!(app.list_state.selected(), Some(2));
+    }
+
+    #[test]
+    fn a_second_launch_right_after_the_first_is_ignored() {
+        let mut app = app_with(&["a"]);
+        app.dry_run = true;
+        app.launch(0);
+        app.launch(0);
+        assert_eq!(app.dry_run_log, vec!["# a".to_string()]);
+        assert_eq!(app.last_result.as_ref().unwrap().text, "Already launching...");
+    }
+
+    #[test]
+    fn running_copies_found_in_the_background_open_the_prompt() {
+        let mut app = app_with(&["a", "b"]);
+        app.checking = Some((PendingLaunch::Group(1), "b".to_string(), false));
+        // Un recuento de otra búsqueda (ya cancelada) no cuenta
+        app.checked_running("a", 3);
+        assert!(app.checking.is_some() && !app.confirming());
+        app.checked_running("b", 2);
+        assert!(app.checking.is_none());
+        assert!(matches!(app.pending, Some(PendingLaunch::Group(1))));
+        assert_eq!(app.pending_running, 2);
+    }
+
+    #[test]
+    fn launch_and_quit_survives_the_background_running_check() {
+        let mut app = app_with(&["a"]);
+        app.config.groups[0].check_running = true;
+        app.dry_run = false;
+        app.select_and_quit();
+        assert!(app.checking.is_some());
+        assert!(app.quit_after_launch);
+        // Nada en marcha: se lanza sin preguntar y `L` sale al terminar
+        app.checked_running("a", 0);
+        assert!(app.quit_when_done);
+        assert!(!app.quit_after_launch);
+    }
+
+    #[test]
+    fn interactive_apps_wait_for_the_group_hooks_and_l_quits_after_them() {
+        let json = r#"{ "groups": [
+            { "name": "solo", "apps": [{ "name": "repl", "command": "sh", "interactive": true }] },
+            { "name": "hooked", "pre": { "name": "pre", "command": "true" },
+              "apps": [{ "name": "repl", "command": "sh", "interactive": true }] }
+        ] }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let mut app = App::new(config, PathBuf::from("launcher.json"), None);
+
+        // Sin nada más que lanzar, la app pasa directo a primer plano
+        app.quit_after_launch = true;
+        app.launch(0);
+        assert_eq!(app.foreground.len(), 1);
+        assert!(app.quit_when_done);
+
+        // Con un hook, espera a que el hilo de lanzamiento lo corra
+        app.foreground.clear();
+        app.last_launch = None;
+        app.launch(1);
+        assert!(app.foreground.is_empty());
+        assert!(app.after_launch.contains_key("hooked"));
+        let start = Instant::now();
+        while app.foreground.is_empty() && start.elapsed() < Duration::from_secs(5) {
+            app.poll_events();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(app.foreground.len(), 1);
+        assert!(app.after_launch.is_empty());
+
+        let mut config: Config = serde_json::from_str(json).unwrap();
+        config.groups[0].sequential = true;
+        assert!(config.validate().unwrap_err()[0].ends_with("part of a sequential group"));
+    }
+
+    #[test]
+    fn next_and_prev_on_a_single_group_stay_put() {
+        let mut app = app_with(&["a"]);
+        app.next();
+        assert_eq!(app.selected, 0);
+        app.prev();
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn navigation_on_an_empty_list_does_nothing() {
+        let mut app = app_with(&[]);
+        app.next();
+        app.prev();
+        assert_eq!(app.selected, 0);
+        assert_eq!(app.selected_group(), None);
+
+        // También con un filtro de búsqueda sin coincidencias
+        let mut app = app_with(&["a", "b"]);
+        app.push_query('z');
+        app.next();
+        app.prev();
+        assert!(app.visible.is_empty());
+        assert_eq!(app.list_state.selected(), None);
+    }
+
+    #[test]
+    fn prev_from_the_top_enters_recent_and_next_returns() {
+        let mut app = app_with(&["a", "b"]);
+        app.recent = vec!["b".to_string()];
+        app.prev();
+        assert_eq!(app.recent_focus, Some(0));
+        app.next();
+        assert_eq!(app.recent_focus, None);
+        assert_eq!(app.selected, 0);
+    }
+
+    #[test]
+    fn committed_search_keeps_the_filter_and_cycles_matches() {
+        let mut app = app_with(&["web-api", "docs", "web-ui", "webhooks"]);
+        app.start_search();
+        "web".chars().for_each(|c| app.push_query(c));
+        app.commit_search();
+        assert!(!app.searching);
+        assert_eq!(app.visible.len(), 3);
+        app.next_match(false);
+        assert_eq!(app.selected, 2);
+        app.next_match(true);
+        assert_eq!(app.selected, 0);
+        app.clear_search();
+        assert_eq!(app.visible.len(), 4);
+    }
+
+    #[test]
+    fn status_messages_are_kept_in_the_session_log() {
+        let mut app = app_with(&["dev"]);
+        app.set_status(StatusMessage::new("Launched dev".to_string(), false));
+        app.log("dev exited (0)".to_string());
+        let texts: Vec<_> = app.messages.iter().map(|(_, text)| text.as_str()).collect();
+        assert_eq!(texts, ["Launched dev", "dev exited (0)"]);
+        assert_eq!(app.last_result.as_ref().map(|m| m.text.as_str()), Some("Launched dev"));
+
+        // Desplazado hacia atrás, una entrada nueva no mueve la vista
+        app.scroll_messages(true);
+        app.log("Config reloaded".to_string());
+        assert_eq!(app.messages_scroll, 2);
+        app.toggle_messages();
+        assert!(app.show_messages);
+        assert_eq!(app.messages_scroll, 0);
+
+        for i in 0..MESSAGE_LOG_LINES {
+            app.log(i.to_string());
+        }
+        assert_eq!(app.messages.len(), MESSAGE_LOG_LINES);
+        assert_eq!(app.messages[0].1, "0");
+    }
+
+    #[test]
+    fn restarts_run_off_the_ui_thread_and_reach_the_launch_log() {
+        let dir = std::env::temp_dir().join(format!("launcher-restart-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let log_file = dir.join("launches.log");
+        let json = r#"{ "groups": [{ "name": "svc", "apps": [
+            { "name": "daemon", "command": "true", "restart": "always" }
+        ] }] }"#;
+        let config: Config = serde_json::from_str(json).unwrap();
+        let group = &config.groups[0];
+        let mut tracked = TrackedApp::new(group, &group.apps[0], Err(io::Error::other("gone")));
+        tracked.restart_at = Some(Instant::now());
+        let opts = LaunchOptions {
+            log_file: Some(log_file.clone()),
+            ..LaunchOptions::default()
+        };
+
+        // Vuelve enseguida; el resultado llega como un evento
+        let (tx, rx) = mpsc::channel();
+        tracked.restart_if_due(&tx, &opts);
+        assert!(tracked.restarting);
+        assert!(tracked.restart_at.is_none());
+        let Ok(LaunchEvent::Restarted { id, res }) = rx.recv_timeout(Duration::from_secs(5))
+        else {
+            panic!("the restart never reported back");
+        };
+        assert_eq!(id, tracked.id);
+        assert_eq!(tracked.restarted(res, None), "↻ daemon restarted (1×)");
+        assert_eq!(tracked.status, AppStatus::Running);
+        assert!(!tracked.restarting);
+
+        let log = std::fs::read_to_string(&log_file).unwrap();
+        assert!(log.contains("[svc] daemon: true"), "{}", log);
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn the_restart_backoff_starts_over_once_the_app_stayed_up() {
+        let json = r#"{ "name": "svc", "apps": [
+            { "name": "daemon", "command": "true", "restart": "always" }
+        ] }"#;
+        let group: Group = serde_json::from_str(json).unwrap();
+        let mut tracked = TrackedApp::new(&group, &group.apps[0], Err(io::Error::other("gone")));
+        let delay = |t: &TrackedApp| t.restart_at.unwrap() - Instant::now();
+
+        // Reinicios seguidos: la espera se duplica
+        tracked.schedule_restart();
+        assert!(delay(&tracked) <= Duration::from_secs(1));
+        tracked.schedule_restart();
+        assert!(delay(&tracked) > Duration::from_secs(1));
+
+        // Tras un buen rato en marcha vuelve a ser la primera
+        tracked.started = Some(Instant::now() - RESTART_BACKOFF_RESET);
+        tracked.schedule_restart();
+        assert!(delay(&tracked) <= Duration::from_secs(1));
+        assert_eq!(tracked.backoff, 1);
+    }
+}